@@ -64,13 +64,6 @@ pub(crate) fn expand(function: Function, spec: Spec) -> TokenStream {
         Ok(crate::core::object::IntoObject::into_obj(val, cx).into())
     };
 
-    let arg_count_guard = match (required as usize, optional as usize, rest) {
-        (r, 0, false) => quote! {arg_cnt != #r},
-        (0, o, false) => quote! {#o < arg_cnt},
-        (r, o, false) => quote! {arg_cnt < #r || #r + #o < arg_cnt},
-        (r, _, true) => quote! {arg_cnt < #r},
-    };
-
     quote! {
         #[automatically_derived]
         #[doc(hidden)]
@@ -79,12 +72,7 @@ pub(crate) fn expand(function: Function, spec: Spec) -> TokenStream {
             env: &mut crate::core::gc::Rt<crate::core::env::Env>,
             cx: &'ob mut crate::core::gc::Context,
         ) -> anyhow::Result<crate::core::object::Object<'ob>> {
-            #[allow(clippy::manual_range_contains)]
-            if #arg_count_guard {
-                let upper = #required + #optional;
-                let expected = if arg_cnt > upper as usize {upper} else {#required};
-                return Err(crate::data::LispError::arg_cnt(#lisp_name, expected, arg_cnt as u16, cx).into());
-            }
+            #struct_name.check_arity(arg_cnt, cx)?;
             #create_args
             #subr_call
         }
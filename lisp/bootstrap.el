@@ -16,3 +16,30 @@
 (bootstrap-check-bytecodes 'byte-compile-normal-call (unibyte-string 137 64 57 131 29 0 194 195 2 64 34 131 29 0 137 64 196 62 131 25 0 197 1 33 136 198 1 33 136 8 131 37 0 199 1 33 136 9 131 61 0 137 64 200 61 131 61 0 194 200 137 34 131 61 0 201 1 64 202 34 136 203 1 64 33 136 204 205 2 65 34 136 206 207 2 65 71 34 135))
 (bootstrap-check-bytecodes 'byte-compile-form (unibyte-string 137 24 1 9 66 17 1 58 132 96 0 1 57 131 69 0 1 198 1 199 62 134 64 0 200 2 33 134 64 0 137 133 64 0 1 10 62 134 64 0 201 2 33 133 64 0 202 49 62 0 1 137 74 76 136 198 48 182 130 130 66 0 136 203 182 130 131 76 0 204 2 33 136 130 166 1 8 131 89 0 11 131 89 0 198 16 130 166 1 205 2 33 136 130 166 1 1 64 57 131 112 1 1 64 137 206 78 1 207 78 134 116 0 1 12 62 2 208 62 131 180 0 4 65 137 58 131 179 0 137 162 137 58 131 178 0 137 162 137 209 61 131 177 0 1 163 137 58 131 176 0 137 162 1 163 137 132 174 0 1 13 62 131 174 0 210 211 212 6 11 5 35 33 136 182 2 136 136 136 136 2 213 62 131 204 0 4 65 162 137 162 209 61 131 203 0 214 1 65 64 198 34 136 136 215 216 33 131 13 1 2 198 1 199 62 134 2 1 200 2 33 134 2 1 137 133 2 1 1 10 62 134 2 1 201 2 33 133 2 1 202 49 0 1 1 137 74 76 136 198 48 182 130 130 4 1 136 203 182 130 131 13 1 217 3 218 5 35 136 215 207 4 34 131 60 1 137 131 60 1 217 3 219 5 4 59 131 43 1 220 221 222 6 7 33 34 130 58 1 4 203 61 132 57 1 211 223 6 6 34 130 58 1 224 36 136 4 64 75 162 225 61 131 80 1 210 220 226 6 7 64 6 8 35 33 136 1 131 104 1 227 2 33 131 104 1 1 228 61 132 104 1 1 5 33 182 4 130 166 1 229 5 33 182 4 130 166 1 230 2 64 33 131 133 1 14 45 231 62 131 133 1 232 2 33 136 130 166 1 1 64 162 233 61 131 162 1 1 234 3 33 137 178 4 61 132 162 1 235 2 8 34 136 198 16 130 166 1 229 2 33 136 8 131 173 1 236 32 136 9 137 65 17 162 41 135))
 (bootstrap-check-bytecodes 'byte-compile-out-toplevel (unibyte-string 1 131 61 0 8 64 64 197 61 131 18 0 8 65 16 130 61 0 198 199 9 158 63 133 59 0 200 9 33 137 131 54 0 137 64 64 57 132 54 0 137 64 64 167 132 54 0 137 65 178 1 130 29 0 137 64 64 178 1 33 136 201 202 203 34 136 8 159 16 10 204 62 131 79 0 205 8 33 16 199 1 206 61 63 199 137 4 206 61 132 121 1 4 207 61 131 102 0 208 130 103 0 209 8 155 132 121 1 210 8 158 132 121 1 202 8 158 137 178 3 131 121 1 1 200 8 33 62 65 159 178 4 3 64 64 211 62 131 241 0 3 64 65 64 178 2 3 64 64 212 61 131 232 0 1 58 132 217 0 1 57 131 232 0 1 199 1 213 62 134 212 0 214 2 33 134 212 0 137 133 212 0 1 11 62 134 212 0 215 2 33 133 212 0 216 49 210 0 1 137 74 76 136 199 48 182 130 130 214 0 136 217 182 130 132 232 0 2 131 117 1 218 2 68 1 66 137 178 2 130 107 1 1 1 66 137 178 2 130 107 1 2 131 117 1 219 4 155 132 117 1 3 64 64 220 78 137 178 3 131 117 1 3 65 131 46 1 4 207 61 131 117 1 3 65 65 131 117 1 221 1 71 5 64 65 34 131 117 1 3 65 64 64 197 61 131 117 1 3 65 178 4 199 178 3 137 159 178 1 1 222 61 131 85 1 137 64 162 218 61 131 85 1 137 64 65 64 57 131 85 1 137 64 65 64 1 65 66 130 88 1 1 1 66 67 178 1 4 207 61 132 110 1 223 199 224 225 4 64 65 34 34 63 131 117 1 3 65 178 4 130 132 0 3 131 131 1 226 32 227 228 8 33 2 12 70 135 137 65 131 141 1 229 1 159 66 135 137 64 135))
+
+;; Constant folding is done by byte-opt.el, which evaluates calls to pure
+;; arithmetic functions whose arguments are all literals using the builtins
+;; in arith.rs. Check that literals fold to a single constant, that folding
+;; stops at the first non-literal argument, and that a call that would
+;; signal is left for runtime.
+(let ((folded (byte-compile #'(lambda () (+ 1 2 3)))))
+  (unless (and (equal (aref folded 1) (unibyte-string 192 135))
+               (equal (aref folded 2) [6]))
+    (error "Constant folding failed: %S" folded)))
+(let ((partial (byte-compile #'(lambda (x) (+ 1 2 x)))))
+  (unless (and (equal (aref partial 1) (unibyte-string 192 1 92 135))
+               (equal (aref partial 2) [3])
+               (= (funcall partial 4) 7))
+    (error "Constant folding with a variable failed: %S" partial)))
+(let ((unfolded (byte-compile #'(lambda () (/ 1 0)))))
+  (unless (equal (aref unfolded 1) (unibyte-string 192 193 165 135))
+    (error "Constant folding of an error failed: %S" unfolded)))
+
+;; macros defined with defmacro expand one step at a time
+(defmacro bootstrap--inc (x) (list '1+ x))
+(defmacro bootstrap--inc2 (x) (list 'bootstrap--inc (list 'bootstrap--inc x)))
+(unless (and (equal (macroexpand-1 '(bootstrap--inc2 1))
+                    '(bootstrap--inc (bootstrap--inc 1)))
+             (equal (macroexpand '(bootstrap--inc2 1)) '(1+ (bootstrap--inc 1)))
+             (= (bootstrap--inc2 1) 3))
+  (error "Macro expansion failed"))
@@ -43,8 +43,9 @@
 
 (defun obarrayp (object)
   "Return t if OBJECT is an obarray."
-  (and (vectorp object)
-       (< 0 (length object))))
+  (or (eq (type-of object) 'obarray)
+      (and (vectorp object)
+           (< 0 (length object)))))
 
 ;; Don’t use obarray as a variable name to avoid shadowing.
 (defun obarray-get (ob name)
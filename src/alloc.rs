@@ -40,6 +40,8 @@ pub(crate) fn make_closure<'ob>(
             prototype.args,
             prototype.depth,
         )
+        .with_doc(prototype.doc())
+        .with_interactive(prototype.interactive())
         .into_obj(cx))
     }
 }
@@ -51,13 +53,17 @@ pub(crate) fn make_byte_code<'ob>(
     byte_code: &'ob ByteString,
     constants: &'ob LispVec,
     depth: usize,
-    _docstring: Option<Object>,
-    _interactive_spec: Option<Object>,
+    docstring: Option<Object>,
+    interactive_spec: Option<Object>,
     _elements: &[Object],
     cx: &'ob Context,
 ) -> Result<&'ob ByteFn> {
+    // TODO: A nil spec (a bare `(interactive)`) can't be told apart from a
+    // missing one here, so those functions are not commands yet.
     unsafe {
-        let bytefn = ByteFn::make(byte_code, constants, FnArgs::from_arg_spec(arglist)?, depth);
+        let bytefn = ByteFn::make(byte_code, constants, FnArgs::from_arg_spec(arglist)?, depth)
+            .with_doc(docstring)
+            .with_interactive(interactive_spec);
         Ok(bytefn.into_obj(cx).untag())
     }
 }
@@ -68,6 +74,11 @@ fn make_vector(length: usize, init: Object) -> Vec<Object> {
     vec![init; length]
 }
 
+#[defun]
+fn make_list<'ob>(length: usize, init: Object<'ob>, cx: &'ob Context) -> Object<'ob> {
+    (0..length).fold(NIL, |acc, _| Cons::new(init, acc, cx).into())
+}
+
 #[defun]
 fn vector<'ob>(objects: &[Object<'ob>]) -> Vec<Object<'ob>> {
     objects.into()
@@ -124,4 +135,40 @@ mod test {
         assert_eq!(record[1].get(), "slot1");
         assert_eq!(record[2].get(), "slot2");
     }
+
+    #[test]
+    fn test_purecopy() {
+        use crate::interpreter::assert_lisp;
+        // the argument is returned as is, so it stays mutable and unshared
+        assert_lisp("(let ((s (purecopy (copy-sequence \"abc\")))) (aset s 0 ?x) s)", "\"xbc\"");
+        assert_lisp(
+            "(eq (purecopy (copy-sequence \"q\")) (purecopy (copy-sequence \"q\")))",
+            "nil",
+        );
+        assert_lisp("(let ((s (copy-sequence \"q\"))) (eq s (purecopy s)))", "t");
+    }
+
+    #[test]
+    fn test_make_list() {
+        use crate::interpreter::assert_lisp;
+        assert_lisp("(make-list 3 'x)", "(x x x)");
+        assert_lisp("(make-list 0 'x)", "nil");
+        assert_lisp("(let ((x (make-list 2 (list 1)))) (eq (car x) (car (cdr x))))", "t");
+        assert_lisp("(condition-case nil (make-list -1 'x) (error 'err))", "err");
+    }
+
+    #[test]
+    fn test_make_symbol_name() {
+        use crate::interpreter::assert_lisp;
+        // the symbol keeps its own copy of the name
+        assert_lisp(
+            "(let* ((s (copy-sequence \"abc\")) (sym (make-symbol s))) (aset s 0 ?z) (list s (symbol-name sym)))",
+            "(\"zbc\" \"abc\")",
+        );
+        // and mutating the result of symbol-name leaves the symbol alone
+        assert_lisp(
+            "(let ((sym (make-symbol \"abc\"))) (aset (symbol-name sym) 0 ?z) (symbol-name sym))",
+            "\"abc\"",
+        );
+    }
 }
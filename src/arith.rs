@@ -1,9 +1,19 @@
 //! Arithmetic operators.
-use crate::core::object::{Gc, IntoObject, Number, NumberType, ObjectType};
+use crate::core::cons::Cons;
+use crate::core::gc::Context;
+use crate::core::object::{
+    Gc, IntoObject, Number, NumberType, Object, ObjectType, MAX_FIXNUM, MIN_FIXNUM,
+};
+use crate::data::LispError;
+use crate::fns::slice_into_list;
+use anyhow::{bail, Result};
 use float_cmp::ApproxEq;
 use rune_macros::defun;
-use std::cmp::PartialEq;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::cmp::{
+    Ordering::{self, Equal, Greater, Less},
+    PartialEq,
+};
+use std::ops::{Add, Div, Mul, Sub};
 
 /// Similar to the object type [NumberType], but contains a float instead of a
 /// reference to a float. This makes it easier to construct and mutate.
@@ -20,6 +30,139 @@ impl Number<'_> {
             NumberType::Float(x) => NumberValue::Float(**x),
         }
     }
+
+    /// Returns true for `0`, `0.0`, and `-0.0`.
+    pub(crate) fn is_zero(self) -> bool {
+        match self.untag() {
+            NumberType::Int(x) => x == 0,
+            NumberType::Float(x) => **x == 0.0,
+        }
+    }
+
+    fn is_float(self) -> bool {
+        matches!(self.untag(), NumberType::Float(_))
+    }
+
+    /// See [NumberValue::checked_div].
+    pub(crate) fn checked_div(
+        self,
+        divisor: Number,
+        mode: Rounding,
+    ) -> Option<(NumberValue, NumberValue)> {
+        self.val().checked_div(divisor.val(), mode)
+    }
+}
+
+/// The rounding modes of `floor`, `ceiling`, `round` and `truncate`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Rounding {
+    Floor,
+    Ceiling,
+    /// Round to nearest, with ties going to the even quotient
+    Round,
+    Truncate,
+}
+
+impl Rounding {
+    /// Turn the truncated quotient and remainder of dividing by `divisor` into
+    /// the ones for this rounding mode.
+    fn adjust<T>(self, quot: T, rem: T, divisor: T) -> (T, T)
+    where
+        T: Copy + PartialOrd + Default + Add<Output = T> + Sub<Output = T> + RoundingValue,
+    {
+        let zero = T::default();
+        if rem == zero {
+            return (quot, rem);
+        }
+        // whether the exact quotient is positive
+        let positive = (rem < zero) == (divisor < zero);
+        let away = match self {
+            Rounding::Truncate => false,
+            Rounding::Floor => !positive,
+            Rounding::Ceiling => positive,
+            Rounding::Round => match rem.abs_doubled().partial_cmp(&divisor.abs_value()) {
+                Some(Greater) => true,
+                Some(Equal) => quot.odd(),
+                _ => false,
+            },
+        };
+        match (away, positive) {
+            (false, _) => (quot, rem),
+            (true, true) => (quot + T::ONE, rem - divisor),
+            (true, false) => (quot - T::ONE, rem + divisor),
+        }
+    }
+}
+
+/// The operations [Rounding::adjust] needs beyond basic arithmetic.
+trait RoundingValue {
+    const ONE: Self;
+    fn abs_value(self) -> Self;
+    fn abs_doubled(self) -> Self;
+    fn odd(self) -> bool;
+}
+
+impl RoundingValue for i64 {
+    const ONE: Self = 1;
+    fn abs_value(self) -> Self {
+        self.saturating_abs()
+    }
+    fn abs_doubled(self) -> Self {
+        self.saturating_abs().saturating_mul(2)
+    }
+    fn odd(self) -> bool {
+        self % 2 != 0
+    }
+}
+
+impl RoundingValue for f64 {
+    const ONE: Self = 1.0;
+    fn abs_value(self) -> Self {
+        self.abs()
+    }
+    fn abs_doubled(self) -> Self {
+        self.abs() * 2.0
+    }
+    fn odd(self) -> bool {
+        self % 2.0 != 0.0
+    }
+}
+
+impl NumberValue {
+    /// Divide by `divisor`, rounding the quotient as `mode` directs, and
+    /// return the quotient and the remainder `self - quotient * divisor`.
+    /// Integer division by zero or an integer quotient that overflows returns
+    /// `None`, while float division follows IEEE and gives a non-finite
+    /// quotient.
+    pub(crate) fn checked_div(
+        self,
+        divisor: NumberValue,
+        mode: Rounding,
+    ) -> Option<(NumberValue, NumberValue)> {
+        use NumberValue as N;
+        match (self, divisor) {
+            (N::Int(x), N::Int(y)) => {
+                let quot = x.checked_div(y)?;
+                let (quot, rem) = mode.adjust(quot, x.wrapping_rem(y), y);
+                Some((N::Int(quot), N::Int(rem)))
+            }
+            (x, y) => {
+                let (x, y) = (x.as_float(), y.as_float());
+                // fmod is exact, which keeps the remainder exact as well
+                let rem = x % y;
+                let quot = ((x - rem) / y).round();
+                let (quot, rem) = mode.adjust(quot, rem, y);
+                Some((N::Float(quot), N::Float(rem)))
+            }
+        }
+    }
+
+    fn as_float(self) -> f64 {
+        match self {
+            NumberValue::Int(x) => x as f64,
+            NumberValue::Float(x) => x,
+        }
+    }
 }
 
 impl IntoObject for NumberValue {
@@ -33,18 +176,23 @@ impl IntoObject for NumberValue {
     }
 }
 
+/// Wrap an integer result, or return `None` if it is not a fixnum.
+pub(crate) fn fixnum(value: Option<i64>) -> Option<NumberValue> {
+    value.filter(|x| (MIN_FIXNUM..=MAX_FIXNUM).contains(x)).map(NumberValue::Int)
+}
+
 fn arith(
     cur: NumberValue,
     next: NumberValue,
-    int_fn: fn(i64, i64) -> i64,
+    int_fn: fn(i64, i64) -> Option<i64>,
     float_fn: fn(f64, f64) -> f64,
-) -> NumberValue {
+) -> Option<NumberValue> {
     use NumberValue as N;
     match (cur, next) {
-        (N::Int(l), N::Int(r)) => N::Int(int_fn(l, r)),
-        (N::Int(l), N::Float(r)) => N::Float(float_fn(l as f64, r)),
-        (N::Float(l), N::Int(r)) => N::Float(float_fn(l, r as f64)),
-        (N::Float(l), N::Float(r)) => N::Float(float_fn(l, r)),
+        (N::Int(l), N::Int(r)) => fixnum(int_fn(l, r)),
+        (N::Int(l), N::Float(r)) => Some(N::Float(float_fn(l as f64, r))),
+        (N::Float(l), N::Int(r)) => Some(N::Float(float_fn(l, r as f64))),
+        (N::Float(l), N::Float(r)) => Some(N::Float(float_fn(l, r))),
     }
 }
 
@@ -52,48 +200,35 @@ fn arith(
 // Arithmetic operators //
 //////////////////////////
 
-impl Neg for NumberValue {
-    type Output = Self;
-    fn neg(self) -> Self::Output {
+// We don't have bignums, so integer results outside of the fixnum range
+// signal an overflow-error instead.
+
+impl NumberValue {
+    pub(crate) fn checked_neg(self) -> Option<Self> {
         match self {
-            NumberValue::Int(x) => NumberValue::Int(-x),
-            NumberValue::Float(x) => NumberValue::Float(-x),
+            NumberValue::Int(x) => fixnum(x.checked_neg()),
+            NumberValue::Float(x) => Some(NumberValue::Float(-x)),
         }
     }
-}
 
-impl Add for NumberValue {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        arith(self, rhs, Add::add, Add::add)
+    pub(crate) fn checked_add(self, rhs: Self) -> Option<Self> {
+        arith(self, rhs, i64::checked_add, Add::add)
     }
-}
 
-impl Sub for NumberValue {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self::Output {
-        arith(self, rhs, Sub::sub, Sub::sub)
+    pub(crate) fn checked_sub(self, rhs: Self) -> Option<Self> {
+        arith(self, rhs, i64::checked_sub, Sub::sub)
     }
-}
 
-impl Mul for NumberValue {
-    type Output = Self;
-    fn mul(self, rhs: Self) -> Self::Output {
-        arith(self, rhs, Mul::mul, Mul::mul)
+    pub(crate) fn checked_mul(self, rhs: Self) -> Option<Self> {
+        arith(self, rhs, i64::checked_mul, Mul::mul)
     }
 }
 
-impl Div for NumberValue {
-    type Output = Self;
-    fn div(self, rhs: Self) -> Self::Output {
-        arith(self, rhs, Div::div, Div::div)
-    }
-}
-
-impl Rem for NumberValue {
-    type Output = Self;
-    fn rem(self, rhs: Self) -> Self::Output {
-        arith(self, rhs, Rem::rem, Rem::rem)
+/// Signal an overflow-error if an integer operation left the fixnum range.
+pub(crate) fn check_overflow(value: Option<NumberValue>, cx: &Context) -> Result<NumberValue> {
+    match value {
+        Some(value) => Ok(value),
+        None => bail!(LispError::overflow_error(cx)),
     }
 }
 
@@ -115,81 +250,121 @@ impl PartialEq<f64> for Number<'_> {
     }
 }
 
+/// Compare an integer and a float exactly. Converting the integer to a float
+/// could round it, so the float is split into its integer and fractional
+/// parts instead.
+fn cmp_int_float(int: i64, float: f64) -> Option<Ordering> {
+    // 2^63, the first float above i64::MAX
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if float.is_nan() {
+        None
+    } else if float >= LIMIT {
+        Some(Less)
+    } else if float < -LIMIT {
+        Some(Greater)
+    } else {
+        let whole = float.trunc();
+        match int.cmp(&(whole as i64)) {
+            Equal => 0.0.partial_cmp(&(float - whole)),
+            ordering => Some(ordering),
+        }
+    }
+}
+
 impl PartialOrd for NumberValue {
-    fn partial_cmp(&self, other: &NumberValue) -> Option<std::cmp::Ordering> {
-        match self {
-            NumberValue::Int(lhs) => match other {
-                NumberValue::Int(rhs) => lhs.partial_cmp(rhs),
-                NumberValue::Float(rhs) => (*lhs as f64).partial_cmp(rhs),
-            },
-            NumberValue::Float(lhs) => match other {
-                NumberValue::Int(rhs) => lhs.partial_cmp(&(*rhs as f64)),
-                NumberValue::Float(rhs) => lhs.partial_cmp(rhs),
-            },
+    fn partial_cmp(&self, other: &NumberValue) -> Option<Ordering> {
+        match (*self, *other) {
+            (NumberValue::Int(lhs), NumberValue::Int(rhs)) => lhs.partial_cmp(&rhs),
+            (NumberValue::Int(lhs), NumberValue::Float(rhs)) => cmp_int_float(lhs, rhs),
+            (NumberValue::Float(lhs), NumberValue::Int(rhs)) => {
+                cmp_int_float(rhs, lhs).map(Ordering::reverse)
+            }
+            (NumberValue::Float(lhs), NumberValue::Float(rhs)) => lhs.partial_cmp(&rhs),
         }
     }
 }
 
 #[defun(name = "+")]
-pub(crate) fn add(vars: &[Number]) -> NumberValue {
-    vars.iter().fold(NumberValue::Int(0), |acc, x| acc + x.val())
+pub(crate) fn add(vars: &[Number], cx: &Context) -> Result<NumberValue> {
+    let init = NumberValue::Int(0);
+    vars.iter()
+        .try_fold(init, |acc, x| check_overflow(acc.checked_add(x.val()), cx))
 }
 
 #[defun(name = "-")]
-pub(crate) fn sub(number: Option<Number>, numbers: &[Number]) -> NumberValue {
+pub(crate) fn sub(number: Option<Number>, numbers: &[Number], cx: &Context) -> Result<NumberValue> {
     match number {
         Some(num) => {
             let num = num.val();
             if numbers.is_empty() {
-                -num
+                check_overflow(num.checked_neg(), cx)
             } else {
-                numbers.iter().fold(num, |acc, x| acc - x.val())
+                numbers
+                    .iter()
+                    .try_fold(num, |acc, x| check_overflow(acc.checked_sub(x.val()), cx))
             }
         }
-        None => NumberValue::Int(0),
+        None => Ok(NumberValue::Int(0)),
     }
 }
 
 #[defun(name = "*")]
-pub(crate) fn mul(numbers: &[Number]) -> NumberValue {
-    numbers.iter().fold(NumberValue::Int(1), |acc, x| acc * x.val())
+pub(crate) fn mul(numbers: &[Number], cx: &Context) -> Result<NumberValue> {
+    let init = NumberValue::Int(1);
+    numbers
+        .iter()
+        .try_fold(init, |acc, x| check_overflow(acc.checked_mul(x.val()), cx))
 }
 
 #[defun(name = "/")]
-pub(crate) fn div(number: Number, divisors: &[Number]) -> NumberValue {
-    divisors.iter().fold(number.val(), |acc, x| acc / x.val())
+pub(crate) fn div(number: Number, divisors: &[Number], cx: &Context) -> Result<NumberValue> {
+    // If any argument is a float, all of the division is done with floats.
+    // Like Emacs, this follows IEEE and dividing by zero gives inf or NaN.
+    if number.is_float() || divisors.iter().any(|x| x.is_float()) {
+        let to_float = |x: &Number| match x.val() {
+            NumberValue::Int(x) => x as f64,
+            NumberValue::Float(x) => x,
+        };
+        let quotient = divisors.iter().fold(to_float(&number), |acc, x| acc / to_float(x));
+        return Ok(NumberValue::Float(quotient));
+    }
+    if divisors.iter().any(|x| x.is_zero()) {
+        bail!(LispError::arith_error(cx));
+    }
+    divisors.iter().try_fold(number.val(), |acc, x| {
+        check_overflow(arith(acc, x.val(), i64::checked_div, Div::div), cx)
+    })
 }
 
 #[defun(name = "1+")]
-pub(crate) fn add_one(number: Number) -> NumberValue {
-    number.val() + NumberValue::Int(1)
+pub(crate) fn add_one(number: Number, cx: &Context) -> Result<NumberValue> {
+    check_overflow(number.val().checked_add(NumberValue::Int(1)), cx)
 }
 
 #[defun(name = "1-")]
-pub(crate) fn sub_one(number: Number) -> NumberValue {
-    number.val() - NumberValue::Int(1)
+pub(crate) fn sub_one(number: Number, cx: &Context) -> Result<NumberValue> {
+    check_overflow(number.val().checked_sub(NumberValue::Int(1)), cx)
 }
 
 #[defun(name = "=")]
 pub(crate) fn num_eq(number: Number, numbers: &[Number]) -> bool {
-    match number.val() {
-        NumberValue::Int(num) => numbers.iter().all(|&x| x == num),
-        NumberValue::Float(num) => numbers.iter().all(|&x| x == num),
-    }
+    let number = number.val();
+    numbers.iter().all(|x| number.partial_cmp(&x.val()) == Some(Equal))
 }
 
 #[defun(name = "/=")]
 pub(crate) fn num_ne(number: Number, numbers: &[Number]) -> bool {
-    match number.val() {
-        NumberValue::Int(num) => numbers.iter().all(|&x| x != num),
-        NumberValue::Float(num) => numbers.iter().all(|&x| x != num),
-    }
+    let number = number.val();
+    numbers.iter().all(|x| number.partial_cmp(&x.val()) != Some(Equal))
 }
 
 fn cmp(number: Number, numbers: &[Number], cmp: fn(&NumberValue, &NumberValue) -> bool) -> bool {
     numbers
         .iter()
-        .try_fold(number.val(), |acc, &x| cmp(&acc, &x.val()).then_some(NumberValue::Int(0)))
+        .try_fold(number.val(), |acc, &x| {
+            let x = x.val();
+            cmp(&acc, &x).then_some(x)
+        })
         .is_some()
 }
 
@@ -223,15 +398,29 @@ fn logand(int_or_markers: &[Gc<i64>]) -> i64 {
     int_or_markers.iter().fold(-1, |accum, x| accum & x.untag())
 }
 
+#[defun]
+fn logcount(value: i64) -> i64 {
+    // negative numbers count the bits of their one's complement
+    let count = if value < 0 { value.count_zeros() } else { value.count_ones() };
+    count.into()
+}
+
 #[defun(name = "mod")]
-pub(crate) fn modulo(x: Number, y: Number) -> NumberValue {
-    x.val() % y.val()
+pub(crate) fn modulo(x: Number, y: Number, cx: &Context) -> Result<NumberValue> {
+    // the remainder of floor division takes the sign of the divisor
+    match x.checked_div(y, Rounding::Floor) {
+        Some((_, rem)) => Ok(rem),
+        None => bail!(LispError::arith_error(cx)),
+    }
 }
 
 #[defun(name = "%")]
-pub(crate) fn remainder(x: i64, y: i64) -> i64 {
+pub(crate) fn remainder(x: i64, y: i64, cx: &Context) -> Result<i64> {
     // TODO: Handle markers
-    x % y
+    if y == 0 {
+        bail!(LispError::arith_error(cx));
+    }
+    Ok(x % y)
 }
 
 #[expect(clippy::trivially_copy_pass_by_ref)]
@@ -264,34 +453,85 @@ pub(crate) fn min(number_or_marker: Number, number_or_markers: &[Number]) -> Num
     number_or_markers.iter().fold(number_or_marker.val(), min_val)
 }
 
+#[defun]
+fn number_sequence<'ob>(
+    from: Number,
+    to: Option<Number>,
+    inc: Option<Number>,
+    cx: &'ob Context,
+) -> Result<Object<'ob>> {
+    let start = from.val();
+    let Some(to) = to.map(Number::val).filter(|&to| start.partial_cmp(&to) != Some(Equal)) else {
+        return Ok(Cons::new1(from, cx).into());
+    };
+    let inc = match inc {
+        Some(inc) if inc.is_zero() => bail!("The increment can not be zero"),
+        Some(inc) => inc.val(),
+        None => NumberValue::Int(1),
+    };
+    let ascending = inc > NumberValue::Int(0);
+    let mut seq = Vec::new();
+    let mut next = start;
+    while if ascending { next <= to } else { next >= to } {
+        seq.push(cx.add(next));
+        // Compute each element from the start rather than accumulating so
+        // float error doesn't build up.
+        let offset = NumberValue::Int(seq.len() as i64).checked_mul(inc);
+        next = check_overflow(offset.and_then(|x| start.checked_add(x)), cx)?;
+    }
+    Ok(slice_into_list(&seq, None, cx))
+}
+
+defvar!(MOST_POSITIVE_FIXNUM, object::MAX_FIXNUM);
+defvar!(MOST_NEGATIVE_FIXNUM, object::MIN_FIXNUM);
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::core::gc::{Context, RootSet};
+    use crate::core::gc::{RootSet, Rt};
+    use crate::interpreter::assert_lisp;
+    use rune_core::macros::root;
 
     #[test]
     fn test_add() {
         let roots = &RootSet::default();
         let cx = &Context::new(roots);
-        assert_eq!(add(&[]), NumberValue::Int(0));
-        assert_eq!(add(&[7.into(), 13.into()]), NumberValue::Int(20));
-        assert_eq!(add(&[1.into(), cx.add_as(2.5)]), NumberValue::Float(3.5));
-        assert_eq!(add(&[0.into(), (-1).into()]), NumberValue::Int(-1));
+        assert_eq!(add(&[], cx).unwrap(), NumberValue::Int(0));
+        assert_eq!(add(&[7.into(), 13.into()], cx).unwrap(), NumberValue::Int(20));
+        assert_eq!(add(&[1.into(), cx.add_as(2.5)], cx).unwrap(), NumberValue::Float(3.5));
+        assert_eq!(add(&[0.into(), (-1).into()], cx).unwrap(), NumberValue::Int(-1));
+    }
+
+    #[test]
+    fn test_add_rooted() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        let nums: Vec<Number> = vec![1.into(), cx.add_as(2.5), 3.into()];
+        root!(nums, cx);
+        cx.garbage_collect(true);
+        assert_eq!(add(Rt::bind_slice(nums, cx), cx).unwrap(), NumberValue::Float(6.5));
+        let half: Number = cx.add_as(0.5);
+        nums.push(half);
+        assert_eq!(add(Rt::bind_slice(&nums[1..], cx), cx).unwrap(), NumberValue::Float(6.0));
     }
 
     #[test]
     fn test_sub() {
-        assert_eq!(sub(None, &[]), NumberValue::Int(0));
-        assert_eq!(sub(Some(7.into()), &[]), NumberValue::Int(-7));
-        assert_eq!(sub(Some(7.into()), &[13.into()]), NumberValue::Int(-6));
-        assert_eq!(sub(Some(0.into()), &[(-1).into()]), NumberValue::Int(1));
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        assert_eq!(sub(None, &[], cx).unwrap(), NumberValue::Int(0));
+        assert_eq!(sub(Some(7.into()), &[], cx).unwrap(), NumberValue::Int(-7));
+        assert_eq!(sub(Some(7.into()), &[13.into()], cx).unwrap(), NumberValue::Int(-6));
+        assert_eq!(sub(Some(0.into()), &[(-1).into()], cx).unwrap(), NumberValue::Int(1));
     }
 
     #[test]
     fn test_mul() {
-        assert_eq!(mul(&[]), NumberValue::Int(1));
-        assert_eq!(mul(&[7.into(), 13.into()]), NumberValue::Int(91));
-        assert_eq!(mul(&[(-1).into(), 1.into()]), NumberValue::Int(-1));
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        assert_eq!(mul(&[], cx).unwrap(), NumberValue::Int(1));
+        assert_eq!(mul(&[7.into(), 13.into()], cx).unwrap(), NumberValue::Int(91));
+        assert_eq!(mul(&[(-1).into(), 1.into()], cx).unwrap(), NumberValue::Int(-1));
     }
 
     #[test]
@@ -299,8 +539,25 @@ mod test {
         let roots = &RootSet::default();
         let cx = &Context::new(roots);
 
-        assert_eq!(div(cx.add_as(12.0), &[]), NumberValue::Float(12.0));
-        assert_eq!(div(12.into(), &[5.into(), 2.into()]), NumberValue::Int(1));
+        assert_eq!(div(cx.add_as(12.0), &[], cx).unwrap(), NumberValue::Float(12.0));
+        assert_eq!(div(12.into(), &[5.into(), 2.into()], cx).unwrap(), NumberValue::Int(1));
+        assert!(div(12.into(), &[0.into()], cx).is_err());
+        assert_eq!(
+            div(12.into(), &[cx.add_as(0.0)], cx).unwrap(),
+            NumberValue::Float(f64::INFINITY)
+        );
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        assert_lisp("(/ 7 2)", "3");
+        assert_lisp("(/ 5 2 2.0)", "1.25");
+        assert_lisp("(condition-case err (/ 1 0) (error err))", "(arith-error)");
+        assert_lisp("(condition-case err (/ 1 2 0) (error err))", "(arith-error)");
+        // float division by zero gives inf
+        assert_lisp("(= (/ 1.0 0.0) (/ 2 0.0) (/ -1.0 -0.0))", "t");
+        assert_lisp("(= (/ -1.0 0) (/ 1 -0.0))", "t");
+        assert_lisp("(list (> (/ 1.0 0.0) 1.0e300) (< (/ 1 -0.0) -1.0e300))", "(t t)");
     }
 
     #[test]
@@ -317,6 +574,116 @@ mod test {
         assert!(!num_eq(float1, &[1.into(), 1.into(), float1_1]));
     }
 
+    #[test]
+    fn test_eq_edge_cases() {
+        // = compares numerically, eql compares representations
+        assert_lisp("(list (= 0.0 -0.0) (eql 0.0 -0.0) (/= 0.0 -0.0))", "(t nil nil)");
+        assert_lisp("(list (= 1 1.0) (eql 1 1.0) (= 1 1.0 1))", "(t nil t)");
+        assert_lisp(
+            "(let ((x (/ 0.0 0.0))) (list (= x x) (eql x x) (/= x x) (< x 1) (>= x 1)))",
+            "(nil t t nil nil)",
+        );
+        // no approximate float equality
+        assert_lisp("(= (+ 0.1 0.2) 0.3)", "nil");
+        // integers that can't be represented as a float compare exactly
+        assert_lisp("(= 9007199254740993 9007199254740992.0)", "nil");
+        assert_lisp("(< 9007199254740992.0 9007199254740993)", "t");
+        assert_lisp("(> 9007199254740993 9007199254740992.0)", "t");
+        assert_lisp("(= 9007199254740992 9007199254740992.0)", "t");
+        assert_lisp(
+            "(list (< 36028797018963967 1.0e300) (> -36028797018963968 -1.0e300))",
+            "(t t)",
+        );
+        assert_lisp("(list (< 1 1.5) (> 2 1.5) (< -1 -0.5) (> -1 -1.5))", "(t t t t)");
+    }
+
+    #[test]
+    fn test_remainder() {
+        assert_lisp("(list (% 7 3) (% -7 3) (% 7 -3))", "(1 -1 1)");
+        assert_lisp("(condition-case err (% 1 0) (error err))", "(arith-error)");
+    }
+
+    #[test]
+    fn test_checked_div() {
+        use NumberValue::{Float, Int};
+        use Rounding::*;
+        // (dividend, divisor, mode, quotient, remainder)
+        let cases = [
+            (7, 2, Floor, 3, 1),
+            (-7, 2, Floor, -4, 1),
+            (7, -2, Floor, -4, -1),
+            (-7, -2, Floor, 3, -1),
+            (7, 2, Ceiling, 4, -1),
+            (-7, 2, Ceiling, -3, -1),
+            (7, -2, Ceiling, -3, 1),
+            (-7, -2, Ceiling, 4, 1),
+            (7, 2, Truncate, 3, 1),
+            (-7, 2, Truncate, -3, -1),
+            (7, -2, Truncate, -3, 1),
+            (-7, -2, Truncate, 3, -1),
+            (7, 2, Round, 4, -1),
+            (5, 2, Round, 2, 1),
+            (-7, 2, Round, -4, 1),
+            (-5, 2, Round, -2, -1),
+            (7, -3, Round, -2, 1),
+            (-8, -3, Round, 3, 1),
+            (6, 3, Floor, 2, 0),
+        ];
+        for (x, y, mode, quot, rem) in cases {
+            assert_eq!(
+                Int(x).checked_div(Int(y), mode),
+                Some((Int(quot), Int(rem))),
+                "({x} / {y}) {mode:?}"
+            );
+            assert_eq!(
+                Float(x as f64).checked_div(Int(y), mode),
+                Some((Float(quot as f64), Float(rem as f64))),
+                "({x}.0 / {y}) {mode:?}"
+            );
+        }
+        assert_eq!(Int(1).checked_div(Int(0), Floor), None);
+        assert_eq!(Int(i64::MIN).checked_div(Int(-1), Floor), None);
+        assert_eq!(Float(-2.5).checked_div(Int(1), Round), Some((Float(-2.0), Float(-0.5))));
+        assert_eq!(Float(5.5).checked_div(Float(2.0), Floor), Some((Float(2.0), Float(1.5))));
+        let Some((Float(quot), _)) = Int(1).checked_div(Float(0.0), Floor) else { panic!() };
+        assert!(!quot.is_finite());
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_lisp(
+            "(list (floor -7 2) (ceiling -7 2) (round -7 2) (truncate -7 2))",
+            "(-4 -3 -4 -3)",
+        );
+        assert_lisp(
+            "(list (floor 2.5) (ceiling 2.5) (round 2.5) (round 3.5) (truncate -2.5))",
+            "(2 3 2 4 -2)",
+        );
+        assert_lisp("(list (floor 7 2.0) (round -1.5))", "(3 -2)");
+        assert_lisp("(list (mod -7 2) (mod 7 -2) (mod 7 2) (mod -7.5 2))", "(1 -1 1 0.5)");
+        assert_lisp("(condition-case err (floor 1 0) (error err))", "(arith-error)");
+        assert_lisp("(condition-case err (mod 1 0) (error err))", "(arith-error)");
+        assert_lisp("(condition-case err (floor 1.0 0.0) (error err))", "(arith-error)");
+        // quotients outside the fixnum range overflow instead of saturating
+        assert_lisp("(condition-case err (truncate 1.0e18) (error err))", "(overflow-error)");
+        assert_lisp("(condition-case err (floor 1.0e30) (error err))", "(overflow-error)");
+        assert_lisp(
+            "(condition-case err (floor -36028797018963968 -1) (error err))",
+            "(overflow-error)",
+        );
+        assert_lisp(
+            "(list (floor 1.0e15) (floor 36028797018963967 1))",
+            "(1000000000000000 36028797018963967)",
+        );
+    }
+
+    #[test]
+    fn test_cmp_chain() {
+        assert_lisp("(list (< 1 2 3) (< 1 5 3) (<= 1 1 2) (<= 2 1 3))", "(t nil t nil)");
+        assert_lisp("(list (> 3 2 1) (> 3 1 2) (>= 2 2 1) (>= 1 2 0))", "(t nil t nil)");
+        assert_lisp("(list (= 1 1 1) (= 1 1 2))", "(t nil)");
+    }
+
     #[test]
     fn test_cmp() {
         let roots = &RootSet::default();
@@ -347,4 +714,101 @@ mod test {
         let cx = &Context::new(roots);
         assert_eq!(logand(&[258.into_obj(cx), 255.into_obj(cx)]), 2);
     }
+
+    #[test]
+    fn test_logcount() {
+        assert_lisp("(logcount 7)", "3");
+        assert_lisp("(logcount 0)", "0");
+        assert_lisp("(logcount -1)", "0");
+        assert_lisp("(logcount -8)", "3");
+        assert_lisp("(condition-case nil (logcount 1.0) (error 'err))", "err");
+    }
+
+    #[test]
+    fn test_fixnum_overflow() {
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        let max = || Number::from(MAX_FIXNUM);
+        let min = || Number::from(MIN_FIXNUM);
+        // results at the boundary are still fixnums
+        assert_eq!(cx.add(add_one((MAX_FIXNUM - 1).into(), cx).unwrap()), MAX_FIXNUM);
+        assert_eq!(cx.add(sub_one((MIN_FIXNUM + 1).into(), cx).unwrap()), MIN_FIXNUM);
+        assert_eq!(cx.add(add(&[max(), min()], cx).unwrap()), -1);
+        assert_eq!(cx.add(sub(Some(max()), &[], cx).unwrap()), -MAX_FIXNUM);
+        // anything past it signals
+        assert!(add_one(max(), cx).is_err());
+        assert!(sub_one(min(), cx).is_err());
+        assert!(add(&[max(), 1.into()], cx).is_err());
+        assert!(sub(Some(min()), &[1.into()], cx).is_err());
+        assert!(sub(Some(min()), &[], cx).is_err());
+        assert!(mul(&[max(), 2.into()], cx).is_err());
+        assert!(mul(&[min(), max()], cx).is_err());
+        assert!(div(min(), &[(-1).into()], cx).is_err());
+        // floats never overflow
+        assert_eq!(
+            add(&[max(), 1.0.into_obj(cx).into()], cx).unwrap(),
+            NumberValue::Float(MAX_FIXNUM as f64 + 1.0)
+        );
+    }
+
+    #[test]
+    fn test_fixnum_overflow_error() {
+        assert_lisp(
+            "(condition-case err (1+ 36028797018963967) (overflow-error err))",
+            "(overflow-error)",
+        );
+        assert_lisp(
+            "(condition-case err (* -36028797018963968 2) (error err))",
+            "(overflow-error)",
+        );
+    }
+
+    #[test]
+    fn test_fixnum_constants() {
+        use crate::core::env::{init_variables, sym, Env};
+        use crate::interpreter::eval;
+        use rune_core::macros::{rebind, root};
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        sym::init_symbols();
+        root!(env, new(Env), cx);
+        init_variables(cx, env);
+        let tests = [
+            ("most-positive-fixnum", MAX_FIXNUM),
+            ("most-negative-fixnum", MIN_FIXNUM),
+            ("(+ most-positive-fixnum 0)", MAX_FIXNUM),
+            ("(read (prin1-to-string most-positive-fixnum))", MAX_FIXNUM),
+            ("(read (prin1-to-string most-negative-fixnum))", MIN_FIXNUM),
+        ];
+        for (expr, expect) in tests {
+            let obj = crate::reader::read(expr, cx).unwrap().0;
+            root!(obj, cx);
+            let value = rebind!(eval(obj, None, env, cx).unwrap());
+            assert_eq!(value, expect, "{expr}");
+        }
+    }
+
+    #[test]
+    fn test_is_zero() {
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        assert!(Number::from(0).is_zero());
+        assert!(cx.add_as::<_, _, NumberType>(0.0).is_zero());
+        assert!(cx.add_as::<_, _, NumberType>(-0.0).is_zero());
+        assert!(!Number::from(1).is_zero());
+        assert!(!cx.add_as::<_, _, NumberType>(f64::MIN_POSITIVE).is_zero());
+    }
+
+    #[test]
+    fn test_number_sequence() {
+        assert_lisp("(number-sequence 1 5)", "(1 2 3 4 5)");
+        assert_lisp("(number-sequence 1 6 2)", "(1 3 5)");
+        assert_lisp("(number-sequence 5 1 -2)", "(5 3 1)");
+        assert_lisp("(number-sequence 0 1 0.25)", "(0 0.25 0.5 0.75 1.0)");
+        assert_lisp("(number-sequence 3)", "(3)");
+        assert_lisp("(number-sequence 3 3.0 0)", "(3)");
+        assert_lisp("(number-sequence 5 1)", "nil");
+        assert_lisp("(number-sequence 1 5 -1)", "nil");
+        assert_lisp("(condition-case nil (number-sequence 1 5 0) (error 'err))", "err");
+    }
 }
@@ -1,15 +1,14 @@
 //! The main bytecode interpeter.
-use crate::core::cons::Cons;
-use crate::core::env::{sym, CallFrame, Env};
+use crate::core::env::{CallFrame, Env};
 use crate::core::gc::{Context, IntoRoot, Rt, Rto, Slot};
 use crate::core::object::{
     ByteFn, ByteString, FnArgs, Function, FunctionType, Gc, LispVec, Object, ObjectType, Symbol,
     WithLifetime, NIL,
 };
 use crate::data::LispError;
-use crate::eval::{ErrorType, EvalError, EvalResult};
+use crate::eval::{handles_error, EvalError, EvalResult};
 use anyhow::{bail, Result};
-use rune_core::macros::{bail_err, rebind, root};
+use rune_core::macros::{rebind, root};
 use rune_macros::{defun, Trace};
 
 mod opcode;
@@ -258,36 +257,17 @@ impl<'ob> RootedVM<'_, '_, '_> {
                 Err(e) => e,
             };
 
-            // we will fix this once we can handle different error types
-            #[expect(clippy::never_loop)]
+            let Some(error) = err.condition(self.env, cx) else {
+                return Err(err);
+            };
+            root!(error, cx);
             while let Some(handler) = self.handlers.bind_mut(cx).pop() {
-                match handler.condition.untag() {
-                    ObjectType::Symbol(sym::ERROR) => {}
-                    ObjectType::Cons(conditions) => {
-                        for condition in conditions {
-                            let condition = condition?;
-                            // TODO: Handle different error symbols
-                            if condition != sym::DEBUG && condition != sym::ERROR {
-                                bail_err!("non-error conditions {condition} not yet supported")
-                            }
-                        }
-                    }
-                    x => bail_err!("Invalid condition handler: {x}"),
+                if !handles_error(*handler.condition, error.car(cx), self.env, cx)? {
+                    continue;
                 }
-
-                let error = if let EvalError { error: ErrorType::Signal(id), .. } = err {
-                    let Some((sym, data)) = self.env.get_exception(id) else {
-                        unreachable!("Exception not found")
-                    };
-                    Cons::new(sym, data, cx)
-                } else {
-                    // TODO: Need to remove the anyhow branch once
-                    // full errors are implemented
-                    Cons::new(sym::ERROR, format!("{err}"), cx)
-                };
                 self.unwind(handler.stack_frame, cx);
                 self.env.stack.truncate(handler.stack_size);
-                self.env.stack.push(Object::from(error));
+                self.env.stack.push(Object::from(error.bind(cx)));
                 self.pc.goto(handler.jump_code);
                 continue 'main;
             }
@@ -506,7 +486,7 @@ impl<'ob> RootedVM<'_, '_, '_> {
                     let newlet = self.env.stack.pop(cx);
                     let idx = self.env.stack.pop(cx);
                     let top = self.env.stack.top();
-                    top.set(data::aset(top.bind(cx), idx.try_into()?, newlet)?);
+                    top.set(data::aset(top.bind(cx), idx.try_into()?, newlet, cx)?);
                 }
                 op::SymbolValue => {
                     let top = self.env.stack.top().bind_as(cx)?;
@@ -540,11 +520,11 @@ impl<'ob> RootedVM<'_, '_, '_> {
                 op::Concat4 => todo!("Concat4 bytecode"),
                 op::Sub1 => {
                     let top = self.env.stack.top();
-                    top.set(cx.add(arith::sub_one(top.bind_as(cx)?)));
+                    top.set(cx.add(arith::sub_one(top.bind_as(cx)?, cx)?));
                 }
                 op::Add1 => {
                     let top = self.env.stack.top();
-                    top.set(cx.add(arith::add_one(top.bind_as(cx)?)));
+                    top.set(cx.add(arith::add_one(top.bind_as(cx)?, cx)?));
                 }
                 op::EqlSign => {
                     let rhs = self.env.stack.pop(cx);
@@ -571,16 +551,21 @@ impl<'ob> RootedVM<'_, '_, '_> {
                     let top = self.env.stack.top();
                     top.set(arith::greater_than_or_eq(top.bind_as(cx)?, v1));
                 }
-                op::Diff => todo!("Diff bytecode"),
+                op::Diff => {
+                    let arg1 = self.env.stack.pop(cx);
+                    let top = self.env.stack.top();
+                    let args = &[arg1.try_into()?];
+                    top.set(cx.add(arith::sub(Some(top.bind_as(cx)?), args, cx)?));
+                }
                 op::Negate => {
                     let top = self.env.stack.top();
-                    top.set(cx.add(arith::sub(top.bind_as(cx)?, &[])));
+                    top.set(cx.add(arith::sub(top.bind_as(cx)?, &[], cx)?));
                 }
                 op::Plus => {
                     let arg1 = self.env.stack.pop(cx);
                     let top = self.env.stack.top();
                     let args = &[top.bind_as(cx)?, arg1.try_into()?];
-                    top.set(cx.add(arith::add(args)));
+                    top.set(cx.add(arith::add(args, cx)?));
                 }
                 op::Max => {
                     let arg1 = self.env.stack.pop(cx);
@@ -598,7 +583,7 @@ impl<'ob> RootedVM<'_, '_, '_> {
                     let arg1 = self.env.stack.pop(cx);
                     let top = self.env.stack.top();
                     let args = &[top.bind_as(cx)?, arg1.try_into()?];
-                    top.set(cx.add(arith::mul(args)));
+                    top.set(cx.add(arith::mul(args, cx)?));
                 }
                 op::Point => todo!("Point bytecode"),
                 op::GotoChar => todo!("GotoChar bytecode"),
@@ -760,8 +745,17 @@ impl<'ob> RootedVM<'_, '_, '_> {
                     let top = self.env.stack.top();
                     top.set(fns::nconc(&[top.bind_as(cx)?, list2.try_into()?])?);
                 }
-                op::Quo => todo!("Quo bytecode"),
-                op::Rem => todo!("Rem bytecode"),
+                op::Quo => {
+                    let arg1 = self.env.stack.pop(cx);
+                    let top = self.env.stack.top();
+                    let args = &[arg1.try_into()?];
+                    top.set(cx.add(arith::div(top.bind_as(cx)?, args, cx)?));
+                }
+                op::Rem => {
+                    let arg1 = self.env.stack.pop(cx);
+                    let top = self.env.stack.top();
+                    top.set(arith::remainder(top.bind_as(cx)?, arg1.try_into()?, cx)?);
+                }
                 op::Numberp => {
                     let top = self.env.stack.top();
                     top.set(data::numberp(top.bind(cx)));
@@ -930,6 +924,8 @@ pub(crate) fn call<'ob>(
 #[cfg(test)]
 mod test {
     use crate::core::{
+        cons::Cons,
+        env::sym,
         gc::RootSet,
         object::{HashTable, IntoObject},
     };
@@ -1056,6 +1052,29 @@ mod test {
         check_bytecode!(bytecode, [0], 0, cx);
     }
 
+    #[test]
+    fn test_constant_programs() {
+        use OpCode::*;
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        sym::init_symbols();
+        // (lambda () "foo")
+        make_bytecode!(bytecode, 0, [Constant0, Return], ["foo"], cx);
+        check_bytecode!(bytecode, [], "foo", cx);
+        // (lambda () (+ 1 2)) without constant folding
+        make_bytecode!(bytecode, 0, [Constant0, Constant1, Plus, Return], [1, 2], cx);
+        check_bytecode!(bytecode, [], 3, cx);
+        // (lambda () (funcall '+ 1 2))
+        make_bytecode!(
+            bytecode,
+            0,
+            [Constant0, Constant1, Constant2, Call2, Return],
+            [sym::ADD, 1, 2],
+            cx
+        );
+        check_bytecode!(bytecode, [], 3, cx);
+    }
+
     #[test]
     fn test_bytecode_call() {
         use OpCode::*;
@@ -1099,6 +1118,25 @@ mod test {
         check_bytecode!(bytecode, [1, 2], 3, cx);
     }
 
+    #[test]
+    fn test_arith_opcodes() {
+        use OpCode::*;
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        // (lambda (x y) (- x y))
+        make_bytecode!(bytecode, 514, [StackRef1, StackRef1, Diff, Return], [], cx);
+        check_bytecode!(bytecode, [7, 3], 4, cx);
+        check_bytecode!(bytecode, [1.5, 2], -0.5, cx);
+        // (lambda (x y) (/ x y))
+        make_bytecode!(bytecode, 514, [StackRef1, StackRef1, Quo, Return], [], cx);
+        check_bytecode!(bytecode, [7, 2], 3, cx);
+        check_bytecode!(bytecode, [7.0, 2], 3.5, cx);
+        // (lambda (x y) (% x y))
+        make_bytecode!(bytecode, 514, [StackRef1, StackRef1, Rem, Return], [], cx);
+        check_bytecode!(bytecode, [7, 3], 1, cx);
+        check_bytecode!(bytecode, [-7, 3], -1, cx);
+    }
+
     #[test]
     fn test_bytecode_variables() {
         use OpCode::*;
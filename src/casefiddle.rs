@@ -275,6 +275,17 @@ mod tests {
         assert_eq!(upcase_initials('X'.into(), cx), 'X');
     }
 
+    #[test]
+    fn test_lisp_dispatch() {
+        use crate::interpreter::assert_lisp;
+        assert_lisp(r#"(upcase "abc")"#, r#""ABC""#);
+        assert_lisp("(upcase ?a)", "65");
+        assert_lisp("(downcase ?A)", "97");
+        assert_lisp(r#"(downcase "ÅÄÖ")"#, r#""åäö""#);
+        assert_lisp(r#"(capitalize "hello world")"#, r#""Hello World""#);
+        assert_lisp("(capitalize ?h)", "72");
+    }
+
     #[cfg(not(miri))] // Uses SIMD
     mod upcase_word {
         use crate::core::gc::Context;
@@ -39,7 +39,8 @@ fn make_string<'ob>(
     multibyte: OptionalFlag,
     cx: &'ob Context,
 ) -> Result<Object<'ob>> {
-    if multibyte.is_some() {
+    // Only ASCII can be stored in a unibyte string
+    if multibyte.is_some() || init > 0x7F {
         let chr = int_to_char(i64::try_from(init)?)?;
         let size = chr.len_utf8();
         let mut string = cx.string_with_capacity(length * size);
@@ -53,3 +54,19 @@ fn make_string<'ob>(
         Ok(cx.add(string))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::interpreter::assert_lisp;
+
+    #[test]
+    fn test_make_string() {
+        // ASCII fill characters produce a unibyte string
+        assert_lisp("(string-equal (make-string 3 ?x) \"xxx\")", "t");
+        assert_lisp("(string-equal (make-string 0 ?x) \"\")", "t");
+        assert_lisp("(make-string 2 ?λ)", "\"λλ\"");
+        assert_lisp("(length (make-string 2 ?λ))", "2");
+        assert_lisp("(make-string 2 ?x t)", "\"xx\"");
+        assert_lisp("(condition-case nil (make-string -1 ?x) (error 'err))", "err");
+    }
+}
@@ -1,6 +1,6 @@
-use super::super::object::{List, ListType, Object, ObjectType};
+use super::super::object::{List, ListType, Object, ObjectType, NIL};
 use super::Cons;
-use crate::core::gc::Rto;
+use crate::core::gc::{Context, Rto};
 use anyhow::{Error, Result};
 
 #[derive(Clone)]
@@ -82,6 +82,39 @@ impl<'ob> Iterator for ElemIter<'ob> {
     }
 }
 
+/// An iterator over the cars of a list that stops at the first cdr that is not
+/// a cons. Unlike [`ElemIter`], reaching the end of a dotted list is not an
+/// error; the terminating value is available from [`CarIter::tail`]. Circular
+/// lists are still reported as an error.
+pub(crate) struct CarIter<'ob> {
+    conses: ConsIter<'ob>,
+    tail: Object<'ob>,
+}
+
+impl<'ob> CarIter<'ob> {
+    /// The cdr of the last cons visited so far. Once the iterator is
+    /// exhausted this is `nil` for a proper list and the final cdr of a dotted
+    /// list.
+    pub(crate) fn tail(&self) -> Object<'ob> {
+        self.tail
+    }
+}
+
+impl<'ob> Iterator for CarIter<'ob> {
+    type Item = Result<Object<'ob>, ConsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.conses.next()? {
+            Ok(cons) => {
+                self.tail = cons.cdr();
+                Some(Ok(cons.car()))
+            }
+            Err(ConsError::NonNilCdr) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub(crate) enum ConsError {
     NonNilCdr,
@@ -160,6 +193,12 @@ impl Cons {
     pub(crate) fn conses(&self) -> ConsIter {
         ConsIter::new(Some(self))
     }
+
+    /// Iterate over the cars of the list, bound to the lifetime of `cx`. See
+    /// [`CarIter`] for how dotted lists are handled.
+    pub(crate) fn iter<'ob>(&self, cx: &'ob Context) -> CarIter<'ob> {
+        CarIter { conses: ConsIter::new(Some(cx.bind(self))), tail: NIL }
+    }
 }
 
 impl<'ob> IntoIterator for &'ob Cons {
@@ -307,6 +346,38 @@ mod test {
         assert_eq!(vec, vec![1, 2, 3, 4]);
     }
 
+    #[test]
+    fn dotted_list() {
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        let cons = Cons::new(1, Cons::new(2, 3, cx), cx);
+        let mut iter = cons.elements();
+        assert_eq!(iter.next().unwrap().unwrap(), 1);
+        assert_eq!(iter.next().unwrap().unwrap(), 2);
+        assert!(iter.next().unwrap().is_err());
+
+        let mut iter = cons.iter(cx);
+        let cars: Vec<_> = iter.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(cars, vec![1, 2]);
+        assert_eq!(iter.tail(), 3);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn car_iter() {
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        let list = list![1, 2, 3; cx];
+        let mut iter = list.as_cons().iter(cx);
+        let cars: Vec<_> = iter.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(cars, vec![1, 2, 3]);
+        assert!(iter.tail().is_nil());
+
+        let cons = list![1; cx];
+        cons.as_cons().set_cdr(cons).unwrap();
+        assert!(cons.as_cons().iter(cx).any(|x| x.is_err()));
+    }
+
     #[test]
     fn circular_list() {
         let roots = &RootSet::default();
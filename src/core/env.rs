@@ -14,7 +14,7 @@ type PropertyMap<'a> = ObjectMap<Slot<Symbol<'a>>, Vec<(Slot<Symbol<'a>>, Slot<O
 pub(crate) struct Env<'a> {
     pub(crate) vars: ObjectMap<Slot<Symbol<'a>>, Slot<Object<'a>>>,
     pub(crate) props: PropertyMap<'a>,
-    pub(crate) catch_stack: Vec<Slot<Object<'a>>>,
+    catch_stack: Vec<Slot<Object<'a>>>,
     exception: (Slot<Object<'a>>, Slot<Object<'a>>),
     #[no_trace]
     exception_id: u32,
@@ -102,6 +102,11 @@ impl<'a> RootedEnv<'a> {
         }
     }
 
+    pub(crate) fn get_prop(&self, symbol: Symbol, propname: Symbol) -> Option<&Rto<Object<'a>>> {
+        let plist = self.props.get(symbol)?;
+        plist.iter().find(|x| x.0 == propname).map(|x| &x.1)
+    }
+
     pub(crate) fn set_exception(&mut self, tag: Object, data: Object) -> u32 {
         self.exception.0.set(tag);
         self.exception.1.set(data);
@@ -119,6 +124,31 @@ impl<'a> RootedEnv<'a> {
         self.vars.insert(var, value);
     }
 
+    /// Make `tag` the innermost active catch.
+    pub(crate) fn push_catch(&mut self, tag: Object) {
+        self.catch_stack.push(tag);
+    }
+
+    pub(crate) fn pop_catch(&mut self) {
+        debug_assert!(!self.catch_stack.is_empty(), "Catch stack was empty");
+        self.catch_stack.pop();
+    }
+
+    /// The tag of the innermost active catch.
+    pub(crate) fn current_catch(&self) -> Option<&Rto<Object<'a>>> {
+        self.catch_stack.last()
+    }
+
+    /// Whether any active catch would receive a throw to `tag`.
+    pub(crate) fn is_catching(&self, tag: Object, cx: &Context) -> bool {
+        self.catch_stack.iter().any(|x| x.bind(cx) == tag)
+    }
+
+    /// The number of dynamic bindings currently in effect.
+    pub(crate) fn binding_depth(&self) -> usize {
+        self.binding_stack.len()
+    }
+
     pub(crate) fn unbind(&mut self, count: u16, cx: &Context) {
         for _ in 0..count {
             match self.binding_stack.bind_mut(cx).pop() {
@@ -131,13 +161,23 @@ impl<'a> RootedEnv<'a> {
         }
     }
 
+    /// The value of `var` outside of any let bindings.
+    pub(crate) fn toplevel_value<'ob>(&self, var: Symbol, cx: &'ob Context) -> Option<Object<'ob>> {
+        // The outermost binding saved the toplevel value
+        match self.binding_stack.iter().find(|x| x.0 == var) {
+            Some(binding) => binding.1.as_ref().map(|x| x.bind(cx)),
+            None => self.vars.get(var).map(|x| x.bind(cx)),
+        }
+    }
+
     pub(crate) fn defvar(&mut self, var: Symbol, value: Object) -> Result<()> {
         // TOOD: Handle `eval-sexp` on defvar, which should always update the
         // value
         if self.vars.get(var).is_none() {
             self.set_var(var, value)?;
-            var.make_special();
         }
+        // The variable is special even if it was already bound
+        var.make_special();
 
         // If this variable was unbound previously in the binding stack,
         // we will bind it to the new value
@@ -182,3 +222,78 @@ impl<'a> RootedEnv<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::gc::RootSet;
+    use rune_core::macros::root;
+
+    #[test]
+    fn test_varbind() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        root!(env, new(Env), cx);
+        let sym = Symbol::new_uninterned("varbind", cx);
+        env.set_var(sym, cx.add(1)).unwrap();
+        env.varbind(sym, cx.add(2), cx);
+        assert_eq!(env.vars.get(sym).unwrap().bind(cx), 2);
+        env.varbind(sym, cx.add(3), cx);
+        assert_eq!(env.vars.get(sym).unwrap().bind(cx), 3);
+        env.unbind(1, cx);
+        assert_eq!(env.vars.get(sym).unwrap().bind(cx), 2);
+        env.unbind(1, cx);
+        assert_eq!(env.vars.get(sym).unwrap().bind(cx), 1);
+    }
+
+    #[test]
+    fn test_varbind_void() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        root!(env, new(Env), cx);
+        let sym = Symbol::new_uninterned("varbind", cx);
+        env.varbind(sym, cx.add(1), cx);
+        assert_eq!(env.vars.get(sym).unwrap().bind(cx), 1);
+        // unbinding should restore the void state
+        env.unbind(1, cx);
+        assert!(env.vars.get(sym).is_none());
+    }
+
+    #[test]
+    fn test_props() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        root!(env, new(Env), cx);
+        let sym = Symbol::new_uninterned("sym", cx);
+        let prop = Symbol::new_uninterned("prop", cx);
+        root!(sym, cx);
+        root!(prop, cx);
+        assert!(env.get_prop(sym.bind(cx), prop.bind(cx)).is_none());
+        env.set_prop(sym.bind(cx), prop.bind(cx), cx.add("value"));
+        cx.garbage_collect(true);
+        let value = env.get_prop(sym.bind(cx), prop.bind(cx)).unwrap();
+        assert_eq!(value.bind(cx), "value");
+        env.set_prop(sym.bind(cx), prop.bind(cx), cx.add(2));
+        assert_eq!(env.get_prop(sym.bind(cx), prop.bind(cx)).unwrap().bind(cx), 2);
+        assert!(env.get_prop(prop.bind(cx), sym.bind(cx)).is_none());
+    }
+
+    #[test]
+    fn test_catch_stack() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        root!(env, new(Env), cx);
+        assert!(env.current_catch().is_none());
+        env.push_catch(cx.add("outer"));
+        env.push_catch(cx.add("inner"));
+        // the tags are only reachable through the env
+        cx.garbage_collect(true);
+        assert_eq!(env.current_catch().unwrap().bind(cx), "inner");
+        assert!(env.is_catching(cx.add("outer"), cx));
+        env.pop_catch();
+        assert_eq!(env.current_catch().unwrap().bind(cx), "outer");
+        assert!(!env.is_catching(cx.add("inner"), cx));
+        env.pop_catch();
+        assert!(env.current_catch().is_none());
+    }
+}
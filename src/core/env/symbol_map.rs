@@ -75,6 +75,16 @@ impl SymbolMap {
     pub(crate) fn get(&self, name: &str) -> Option<Symbol> {
         self.map.get(name)
     }
+
+    /// Remove `name` from the map. The symbol itself lives in the global block
+    /// and stays valid; it just can no longer be found by name.
+    pub(crate) fn remove(&mut self, name: &str) -> Option<Symbol<'_>> {
+        self.map.map.remove(name).map(|x| unsafe { x.with_lifetime() })
+    }
+
+    pub(crate) fn symbols(&self) -> impl Iterator<Item = Symbol<'static>> + '_ {
+        self.map.map.values().copied()
+    }
 }
 
 // This file includes all symbol definitions. Generated by build.rs
@@ -2,8 +2,10 @@ use super::GcState;
 use super::Trace;
 use crate::core::object::GcString;
 use crate::core::object::LispHashTable;
+use crate::core::object::LispString;
 use crate::core::object::{Gc, IntoObject, Object, UninternedSymbolMap, WithLifetime};
 use bumpalo::collections::Vec as GcVec;
+use rune_core::hashmap::HashMap;
 use std::cell::{Cell, RefCell};
 use std::fmt::Debug;
 use std::ops::Deref;
@@ -39,6 +41,9 @@ pub(crate) struct Block<const CONST: bool> {
     // track of the memory and free it only after the table is garbage
     // collected. Kind of a hack.
     pub(in crate::core) lisp_hashtables: RefCell<Vec<*const LispHashTable>>,
+    // Strings allocated with `add_interned_string`. This is a weak table; an
+    // entry is removed once its string is no longer reachable.
+    pub(in crate::core) interned_strings: RefCell<HashMap<String, *const LispString>>,
     pub(in crate::core) uninterned_symbol_map: UninternedSymbolMap,
 }
 
@@ -118,6 +123,21 @@ impl<const CONST: bool> Block<CONST> {
         obj.into_obj(self).into()
     }
 
+    /// Allocate a read-only string, reusing a previous allocation from this
+    /// block if an equal string was already interned. Unlike [`Block::add`],
+    /// two calls with the same contents will return objects that are `eq`.
+    #[cfg_attr(not(test), expect(dead_code))]
+    pub(crate) fn add_interned_string<'ob>(&'ob self, string: &str) -> Object<'ob> {
+        if let Some(&ptr) = self.interned_strings.borrow().get(string) {
+            return self.add(unsafe { &*ptr });
+        }
+        let obj = self.add_as::<_, _, &LispString>(string);
+        let ptr: *const LispString = obj.untag();
+        obj.untag().set_read_only();
+        self.interned_strings.borrow_mut().insert(string.to_owned(), ptr);
+        obj.into()
+    }
+
     /// Create a new String whose backing storage is already part of the GC
     /// heap. Does not require dropping when moved during garbage collection
     /// (unlike std::string).
@@ -141,14 +161,21 @@ impl<'ob, 'rt> Context<'rt> {
     const MIN_GC_BYTES: usize = 2000;
     const GC_GROWTH_FACTOR: usize = 12; // divide by 10
     pub(crate) fn new(roots: &'rt RootSet) -> Self {
-        Self { block: Block::new_local(), root_set: roots, next_limit: Self::MIN_GC_BYTES }
+        Self::from_parts(Block::new_local(), roots)
     }
 
     pub(crate) fn from_block(block: Block<false>, roots: &'rt RootSet) -> Self {
         Block::assert_unique();
+        Self::from_parts(block, roots)
+    }
+
+    fn from_parts(block: Block<false>, roots: &'rt RootSet) -> Self {
         Context { block, root_set: roots, next_limit: Self::MIN_GC_BYTES }
     }
 
+    /// Rebind `obj` to the lifetime of this context. Garbage collection needs
+    /// `&mut Context`, so the result can't be used after the next collection.
+    /// This is only valid if `obj` is rooted or was allocated in this context.
     pub(crate) fn bind<T>(&'ob self, obj: T) -> <T as WithLifetime<'ob>>::Out
     where
         T: WithLifetime<'ob>,
@@ -156,13 +183,31 @@ impl<'ob, 'rt> Context<'rt> {
         unsafe { obj.with_lifetime() }
     }
 
+    /// Scoped version of [`Context::bind`]. The rebound object is only
+    /// available inside `f`, and nothing borrowed from it can be returned.
+    pub(crate) fn with_bound<T, R>(
+        &self,
+        obj: T,
+        f: impl for<'a> FnOnce(<T as WithLifetime<'a>>::Out) -> R,
+    ) -> R
+    where
+        T: for<'a> WithLifetime<'a>,
+    {
+        f(unsafe { obj.with_lifetime() })
+    }
+
     pub(crate) fn get_root_set(&'ob self) -> &'rt RootSet {
         self.root_set
     }
 
+    /// True when enough has been allocated since the last collection that
+    /// the next call to `garbage_collect(false)` will collect.
+    pub(crate) fn gc_needed(&self) -> bool {
+        self.block.objects.allocated_bytes() >= self.next_limit
+    }
+
     pub(crate) fn garbage_collect(&mut self, force: bool) {
-        let bytes = self.block.objects.allocated_bytes();
-        if cfg!(not(test)) && !force && bytes < self.next_limit {
+        if cfg!(not(test)) && !force && !self.gc_needed() {
             return;
         }
 
@@ -177,7 +222,8 @@ impl<'ob, 'rt> Context<'rt> {
 
         state.trace_stack();
 
-        self.next_limit = (state.to_space.allocated_bytes() * Self::GC_GROWTH_FACTOR) / 10;
+        let grown = (state.to_space.allocated_bytes() * Self::GC_GROWTH_FACTOR) / 10;
+        self.next_limit = grown.max(Self::MIN_GC_BYTES);
         self.block.drop_stack.borrow_mut().clear();
         // Find all hashtables that have not been moved (i.e. They are no longer
         // accessible) and drop them. Otherwise, update the object pointer.
@@ -192,6 +238,18 @@ impl<'ob, 'rt> Context<'rt> {
             }
         });
 
+        // Same as above, but for interned strings, which are not kept alive by
+        // the table itself.
+        self.block.interned_strings.borrow_mut().retain(|_, ptr| {
+            let string = unsafe { &**ptr };
+            if let Some(fwd) = string.forwarding_ptr() {
+                *ptr = fwd.as_ptr().cast::<LispString>();
+                true
+            } else {
+                false
+            }
+        });
+
         self.block.objects = state.to_space;
     }
 }
@@ -231,10 +289,52 @@ mod test {
     };
 
     use super::*;
+
     fn bind_to_mut<'ob>(cx: &'ob mut Context) -> Object<'ob> {
         cx.add("invariant")
     }
 
+    #[test]
+    fn test_with_bound() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        let obj = cx.add("foo");
+        root!(obj, cx);
+        cx.garbage_collect(true);
+        let len = cx.with_bound(obj.bind(cx), |bound| match bound.untag() {
+            ObjectType::String(s) => s.len(),
+            _ => unreachable!(),
+        });
+        assert_eq!(len, 3);
+    }
+
+    #[test]
+    fn test_gc_threshold() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        assert!(!cx.gc_needed());
+        for i in 0..100 {
+            let _ = cx.add(format!("string {i}"));
+        }
+        assert!(cx.gc_needed());
+        cx.garbage_collect(false);
+        assert!(!cx.gc_needed());
+    }
+
+    #[test]
+    fn test_add() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        assert!(matches!(cx.add(7).untag(), ObjectType::Int(7)));
+        assert!(matches!(cx.add(1.5).untag(), ObjectType::Float(x) if **x == 1.5));
+        assert!(matches!(cx.add("str").untag(), ObjectType::String(x) if x == "str"));
+        let sym = Symbol::new_uninterned("sym", cx);
+        assert!(matches!(cx.add(sym).untag(), ObjectType::Symbol(x) if x == sym));
+        assert!(matches!(cx.add(crate::core::env::sym::TRUE).untag(), ObjectType::Symbol(_)));
+        let cons: Gc<&Cons> = cx.add_as(Cons::new(1, 2, cx));
+        assert_eq!(cons.untag().car(), 1);
+    }
+
     #[test]
     fn test_reborrow() {
         let roots = &RootSet::default();
@@ -288,4 +388,42 @@ mod test {
         assert_eq!(**float, 1.5);
         assert_eq!(int, 1);
     }
+
+    #[test]
+    fn test_interned_string() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        let first = cx.add_interned_string("x");
+        let second = cx.add_interned_string("x");
+        assert!(first.ptr_eq(second));
+        let other = cx.add_interned_string("y");
+        assert!(!first.ptr_eq(other));
+        let ObjectType::String(string) = first.untag() else { unreachable!() };
+        assert!(string.is_read_only());
+        assert!(string.set_char(0, 'z', cx).is_err());
+        assert!(string.clear().is_err());
+        assert_eq!(string, "x");
+        // regular allocations are never shared
+        let first = cx.add("x");
+        let second = cx.add("x");
+        assert_eq!(first, second);
+        assert!(!first.ptr_eq(second));
+    }
+
+    #[test]
+    fn test_interned_string_gc() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        let string = cx.add_interned_string("kept");
+        root!(string, cx);
+        cx.add_interned_string("dropped");
+        cx.garbage_collect(true);
+        assert_eq!(cx.interned_strings.borrow().len(), 1);
+        let again = cx.add_interned_string("kept");
+        assert!(string.bind(cx).ptr_eq(again));
+        // the copy made by the collector is still read-only
+        let ObjectType::String(again) = again.untag() else { unreachable!() };
+        assert!(again.set_char(0, 'K', cx).is_err());
+        assert_eq!(again, "kept");
+    }
 }
@@ -459,17 +459,6 @@ impl<T> Rt<Slot<Gc<T>>> {
         // SAFETY: This is safe because all Gc types have the same representation
         unsafe { &*((self as *const Self).cast::<Rt<Slot<Gc<U>>>>()) }
     }
-
-    // TODO: Find a way to remove this method. We should never need to guess
-    // if something is cons
-    pub(crate) fn as_cons(&self) -> &Rt<Slot<Gc<&Cons>>> {
-        match self.inner().as_obj().untag() {
-            crate::core::object::ObjectType::Cons(_) => unsafe {
-                &*(self as *const Self).cast::<Rt<Slot<Gc<&Cons>>>>()
-            },
-            x => panic!("attempt to convert type that was not cons: {x}"),
-        }
-    }
 }
 
 impl From<&Rt<Slot<Object<'_>>>> for OptionalFlag {
@@ -490,6 +479,11 @@ impl<'a> Rt<Slot<Object<'a>>> {
             unsafe { Ok(Some(&*((self as *const Self).cast::<Rt<Slot<Gc<T>>>>()))) }
         }
     }
+
+    /// Downcast to a rooted cons, or `None` if the object is not a cons.
+    pub(crate) fn as_cons(&self) -> Option<&Rt<Slot<Gc<&'a Cons>>>> {
+        self.try_as().ok()
+    }
 }
 
 impl IntoObject for &Rt<Slot<Object<'_>>> {
@@ -524,6 +518,10 @@ impl Rt<Slot<&Cons>> {
     pub(crate) fn cdr<'ob>(&self, cx: &'ob Context) -> Object<'ob> {
         self.bind(cx).cdr()
     }
+
+    pub(crate) fn set_cdr(&self, new_cdr: Object, cx: &Context) -> anyhow::Result<()> {
+        self.bind(cx).set_cdr(new_cdr)
+    }
 }
 
 impl<T, U> Deref for Rt<(T, U)> {
@@ -614,10 +612,11 @@ impl<T> Rt<Vec<T>> {
 impl<T> Rt<Vec<T>> {
     pub(crate) fn extend_from_slice<U: IntoRoot<T> + Copy>(&mut self, src: &[U]) {
         // TODO: Slot fix extend_from_slice
-        let inner = self.inner_mut();
-        for x in src {
-            inner.push(unsafe { x.into_root() })
-        }
+        self.extend(src.iter().copied());
+    }
+
+    pub(crate) fn extend<U: IntoRoot<T>>(&mut self, iter: impl IntoIterator<Item = U>) {
+        self.inner_mut().extend(iter.into_iter().map(|x| unsafe { x.into_root() }));
     }
 }
 
@@ -657,7 +656,6 @@ impl<T, I: SliceIndex<[Rt<T>]>> IndexMut<I> for Rt<Vec<T>> {
     }
 }
 
-#[derive(Debug)]
 #[repr(transparent)]
 /// A HashMap that can hold values past garbage collection.
 ///
@@ -711,6 +709,13 @@ where
     }
 }
 
+impl<K: Debug, V: Debug> Debug for ObjectMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let map = unsafe { &*self.0.get() };
+        f.debug_map().entries(map.iter()).finish()
+    }
+}
+
 impl<K, V> Trace for ObjectMap<K, V>
 where
     K: Trace + Hash + Eq,
@@ -762,6 +767,51 @@ mod test {
         assert_eq!(vec.bind_ref(cx)[0..3], vec![NIL, str1, str2]);
     }
 
+    #[test]
+    fn test_debug() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        root!(vec, new(Vec<Slot<Object>>), cx);
+        vec.push(cx.add("foo"));
+        vec.push(Object::from(7));
+        assert_eq!(format!("{vec:?}"), "[\"foo\", 7]");
+        type Map<'a> = ObjectMap<Slot<Object<'a>>, Slot<Object<'a>>>;
+        root!(map, new(Map), cx);
+        map.insert(Object::from(1), cx.add("one"));
+        assert_eq!(format!("{map:?}"), "{1: \"one\"}");
+    }
+
+    #[test]
+    fn test_vec_extend() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        root!(vec, new(Vec<Slot<Object>>), cx);
+        vec.push(cx.add("first"));
+        let items = [cx.add("second"), cx.add(3), cx.add("fourth")];
+        vec.extend_from_slice(&items);
+        vec.extend((5..7).map(Object::from));
+        cx.garbage_collect(true);
+        assert_eq!(vec.len(), 6);
+        let vals: Vec<_> = vec.iter().map(|x| x.bind(cx)).collect();
+        assert_eq!(vals[0], "first");
+        assert_eq!(vals[1], "second");
+        assert_eq!(vals[2], 3);
+        assert_eq!(vals[3], "fourth");
+        assert_eq!(vals[4..], [5, 6]);
+    }
+
+    #[test]
+    fn test_vec_iter() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        root!(vec, new(Vec<Slot<Object>>), cx);
+        for i in 1..=4 {
+            vec.push(Object::from(i));
+        }
+        let sum: i64 = vec.iter().map(|x| i64::try_from(x.bind(cx)).unwrap()).sum();
+        assert_eq!(sum, 10);
+    }
+
     #[test]
     fn test_object_map() {
         type Map<'a> = ObjectMap<Slot<Object<'a>>, Slot<Object<'a>>>;
@@ -776,4 +826,95 @@ mod test {
         let val = map.get(key.bind(cx)).unwrap().bind(cx);
         assert_eq!(val, "val");
     }
+
+    #[test]
+    fn test_float_equality() {
+        let root = &RootSet::default();
+        let cx = &mut Context::new(root);
+        let nan = cx.add(f64::NAN);
+        let zero = cx.add(0.0);
+        root!(nan, cx);
+        root!(zero, cx);
+        cx.garbage_collect(true);
+        assert_eq!(*nan, cx.add(f64::NAN));
+        assert_eq!(*zero, cx.add(0.0));
+        assert_ne!(*zero, cx.add(-0.0));
+        assert_ne!(*zero, cx.add(0));
+        root!(vec, new(Vec), cx);
+        vec.push(cx.add(f64::NAN));
+        vec.push(cx.add(-0.0));
+        let expected = vec![cx.add(f64::NAN), cx.add(-0.0)];
+        assert_eq!(vec.bind_ref(cx)[..], expected);
+    }
+
+    #[test]
+    fn test_object_map_remove() {
+        type Map<'a> = ObjectMap<Slot<Object<'a>>, Slot<Object<'a>>>;
+        let root = &RootSet::default();
+        let cx = &mut Context::new(root);
+        root!(map, new(Map), cx);
+        map.insert(cx.add(1), cx.add("one"));
+        map.insert(cx.add(2), cx.add("two"));
+        map.remove(cx.add(1));
+        cx.garbage_collect(true);
+        assert!(map.get(cx.add(1)).is_none());
+        assert_eq!(map.get(cx.add(2)).unwrap().bind(cx), "two");
+        // removing a missing key is a no-op
+        map.remove(cx.add(3));
+        assert_eq!(map.get(cx.add(2)).unwrap().bind(cx), "two");
+    }
+
+    #[test]
+    fn test_derived_fields() {
+        use crate::core::object::Symbol;
+        // #[derive(Trace)] gives rooted access to every field
+        #[derive(Default, rune_macros::Trace)]
+        struct Fields<'a> {
+            map: ObjectMap<Slot<Symbol<'a>>, Slot<Object<'a>>>,
+            tags: Vec<Slot<Object<'a>>>,
+            #[no_trace]
+            count: usize,
+        }
+        let root = &RootSet::default();
+        let cx = &mut Context::new(root);
+        root!(fields, new(Fields), cx);
+        let key = Symbol::new_uninterned("key", cx);
+        fields.map.insert(key, cx.add("val"));
+        fields.tags.push(cx.add("tag"));
+        fields.count += 1;
+        root!(key, cx);
+        cx.garbage_collect(true);
+        let val = fields.map.get(key.bind(cx)).unwrap();
+        assert_eq!(val.bind(cx), "val");
+        assert_eq!(fields.tags[0].bind(cx), "tag");
+        assert_eq!(fields.count, 1);
+    }
+
+    #[test]
+    fn test_cons_mutation() {
+        let root = &RootSet::default();
+        let cx = &mut Context::new(root);
+        let cons = Cons::new(NIL, NIL, cx);
+        root!(cons, cx);
+        cons.bind(cx).set_car(cx.add("car")).unwrap();
+        cons.set_cdr(cx.add("cdr"), cx).unwrap();
+        cx.garbage_collect(true);
+        assert_eq!(cons.car(cx), "car");
+        assert_eq!(cons.cdr(cx), "cdr");
+        assert_eq!(cons.bind(cx).car(), "car");
+    }
+
+    #[test]
+    fn test_downcast() {
+        let root = &RootSet::default();
+        let cx = &mut Context::new(root);
+        let cons: Object = Cons::new(1, 2, cx).into();
+        root!(cons, cx);
+        let num = cx.add(7);
+        root!(num, cx);
+        cx.garbage_collect(true);
+        let as_cons = cons.as_cons().unwrap();
+        assert_eq!(as_cons.untag(cx).car(), 1);
+        assert!(num.as_cons().is_none());
+    }
 }
@@ -8,7 +8,7 @@ use std::fmt::{Debug, Display};
 /// types to be used in match statements if they derive Eq. Even if you never
 /// actually use that field in a match. So we need a float wrapper that
 /// implements that trait.
-#[derive(PartialEq, Trace)]
+#[derive(Trace)]
 pub(crate) struct LispFloat(GcHeap<f64>);
 
 derive_GcMoveable!(LispFloat);
@@ -31,6 +31,15 @@ impl Trace for f64 {
     fn trace(&self, _: &mut GcState) {}
 }
 
+// Floats compare by their bit pattern, matching `eql`. This makes `NaN` equal
+// to itself and distinguishes `0.0` from `-0.0`, which is also what makes the
+// `Eq` impl sound.
+impl PartialEq for LispFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
 impl Eq for LispFloat {}
 
 impl<'new> CloneIn<'new, &'new LispFloat> for LispFloat {
@@ -24,6 +24,9 @@ pub(crate) struct ByteFnPrototype {
     pub(super) op_codes: Box<[u8]>,
     // TODO: remove a level of pointer indirection here.
     pub(super) constants: Slot<&'static LispVec>,
+    pub(super) doc: Option<Slot<Object<'static>>>,
+    /// The argument of the function's `interactive` form, if it is a command.
+    pub(super) interactive: Option<Slot<Object<'static>>>,
 }
 
 /// A function implemented in lisp. Note that all functions are byte compiled,
@@ -74,11 +77,38 @@ impl ByteFn {
             op_codes,
             args,
             depth,
+            doc: None,
+            interactive: None,
         }
     }
 }
 
 impl ByteFnPrototype {
+    /// Mark this function as a command with the given interactive spec.
+    ///
+    /// SAFETY: Like the constants, `spec` must be part of the same block as
+    /// the function.
+    pub(crate) unsafe fn with_interactive(mut self, spec: Option<Object>) -> Self {
+        self.interactive = spec.map(|x| unsafe { Slot::new(x.with_lifetime()) });
+        self
+    }
+
+    pub(crate) fn interactive(&self) -> Option<Object<'_>> {
+        self.interactive.as_ref().map(|x| **x)
+    }
+
+    /// Attach a docstring to this function.
+    ///
+    /// SAFETY: `doc` must be part of the same block as the function.
+    pub(crate) unsafe fn with_doc(mut self, doc: Option<Object>) -> Self {
+        self.doc = doc.map(|x| unsafe { Slot::new(x.with_lifetime()) });
+        self
+    }
+
+    pub(crate) fn doc(&self) -> Option<Object<'_>> {
+        self.doc.as_ref().map(|x| **x)
+    }
+
     pub(crate) fn codes(&self) -> &[u8] {
         &self.op_codes
     }
@@ -93,20 +123,33 @@ impl ByteFnPrototype {
             1 => Some(cx.add(self.codes().to_vec())),
             2 => Some(cx.add(self.consts())),
             3 => Some(self.depth.into()),
+            4 if self.len() > 4 => Some(cx.bind(self.doc().unwrap_or(super::NIL))),
+            5 => self.interactive().map(|x| cx.bind(x)),
             _ => None,
         }
     }
 
     pub(crate) const fn len(&self) -> usize {
-        4
+        if self.interactive.is_some() {
+            6
+        } else if self.doc.is_some() {
+            5
+        } else {
+            4
+        }
     }
 }
 
 impl<'new> CloneIn<'new, &'new Self> for ByteFn {
     fn clone_in<const C: bool>(&self, bk: &'new Block<C>) -> super::Gc<&'new Self> {
         let constants = self.constants.clone_in(bk);
-        let byte_fn =
-            unsafe { ByteFn::make(&self.op_codes, constants.untag(), self.args, self.depth) };
+        let doc = self.doc().map(|x| x.clone_in(bk));
+        let interactive = self.interactive().map(|x| x.clone_in(bk));
+        let byte_fn = unsafe {
+            ByteFn::make(&self.op_codes, constants.untag(), self.args, self.depth)
+                .with_doc(doc)
+                .with_interactive(interactive)
+        };
         byte_fn.into_obj(bk)
     }
 }
@@ -117,7 +160,14 @@ impl Display for ByteFn {
         let code = display_slice(&self.op_codes);
         let consts = display_slice(&self.constants);
         let depth = self.depth;
-        write!(f, "#[{spec} {code} {consts} {depth}]")
+        let doc = self.doc().unwrap_or(super::NIL);
+        match self.interactive() {
+            Some(interactive) => {
+                write!(f, "#[{spec} {code} {consts} {depth} {doc} {interactive}]")
+            }
+            None if self.doc.is_some() => write!(f, "#[{spec} {code} {consts} {depth} {doc}]"),
+            None => write!(f, "#[{spec} {code} {consts} {depth}]"),
+        }
     }
 }
 
@@ -192,6 +242,19 @@ impl SubrFn {
     ) -> Result<Object<'ob>> {
         (self.subr)(arg_cnt, env, cx)
     }
+
+    /// Check that `nargs` is a valid number of arguments for this function,
+    /// returning a `wrong-number-of-arguments` error if not.
+    pub(crate) fn check_arity(&self, nargs: usize, cx: &Context) -> Result<()> {
+        let FnArgs { required, optional, rest, .. } = self.args;
+        let max = required + optional;
+        if nargs < required as usize || (!rest && nargs > max as usize) {
+            let expected = if nargs > max as usize { max } else { required };
+            let err = crate::data::LispError::arg_cnt(self.name, expected, nargs as u16, cx);
+            return Err(err.into());
+        }
+        Ok(())
+    }
 }
 
 impl<'new> WithLifetime<'new> for &SubrFn {
@@ -243,4 +306,46 @@ mod test {
         assert!(FnArgs::from_arg_spec(1).is_err());
         assert!(FnArgs::from_arg_spec(0xFFFF).is_err());
     }
+
+    #[test]
+    fn test_constants_traced() {
+        use crate::core::gc::RootSet;
+        use crate::core::object::{Function, FunctionType, Gc};
+        use rune_core::macros::root;
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        let consts = vec![cx.add("constant"), cx.add(1.5)];
+        let consts: Gc<&LispVec> = cx.add_as(consts);
+        let func = unsafe { ByteFn::make(&[0], consts.untag(), FnArgs::default(), 0) };
+        let func: Function = func.into_obj(cx).into();
+        root!(func, cx);
+        // only the function is rooted, so the constants must be traced through it
+        cx.garbage_collect(true);
+        let FunctionType::ByteFn(func) = func.untag(cx) else { unreachable!() };
+        assert_eq!(func.consts()[0], "constant");
+        assert_eq!(func.consts()[1], 1.5);
+    }
+
+    #[test]
+    fn test_check_arity() {
+        use crate::core::gc::RootSet;
+        fn dummy<'ob>(_: usize, _: &mut Rt<Env>, _: &'ob mut Context) -> Result<Object<'ob>> {
+            Ok(super::super::NIL)
+        }
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        let args = FnArgs { required: 1, optional: 1, rest: false, advice: false };
+        let subr = SubrFn { subr: dummy, args, name: "bar" };
+        assert!(subr.check_arity(0, cx).is_err());
+        assert!(subr.check_arity(1, cx).is_ok());
+        assert!(subr.check_arity(2, cx).is_ok());
+        let err = subr.check_arity(3, cx).unwrap_err();
+        assert_eq!(err.to_string(), "Error: (wrong-number-of-arguments \"bar\" 2 3)");
+
+        let args = FnArgs { required: 1, optional: 0, rest: true, advice: false };
+        let subr = SubrFn { subr: dummy, args, name: "bar" };
+        assert!(subr.check_arity(0, cx).is_err());
+        assert!(subr.check_arity(1, cx).is_ok());
+        assert!(subr.check_arity(100, cx).is_ok());
+    }
 }
@@ -1,5 +1,6 @@
 use super::{CloneIn, IntoObject};
 use crate::core::gc::{AllocState, Block, GcHeap, GcMoveable, GcState, Trace};
+use anyhow::{bail, Result};
 use std::cell::Cell;
 use std::fmt::{Debug, Display};
 use std::ops::Deref;
@@ -15,7 +16,11 @@ pub(crate) struct LispString(GcHeap<LispStringInner>);
 //
 // Case 2: The new char is a different size:
 // Need to allocate a new string and update the cell to point to that.
-struct LispStringInner(Cell<*mut str>);
+struct LispStringInner {
+    string: Cell<*mut str>,
+    // Strings shared by `Block::add_interned_string` can't be mutated
+    read_only: Cell<bool>,
+}
 
 impl GcMoveable for LispString {
     type Value = std::ptr::NonNull<LispString>;
@@ -28,6 +33,7 @@ impl GcMoveable for LispString {
                 let ptr = {
                     let mut new = GcString::from_str_in(self, to_space);
                     let lisp_str = unsafe { LispString::new(new.as_mut_str(), false) };
+                    lisp_str.0.read_only.set(self.0.read_only.get());
                     std::mem::forget(new);
                     let alloc = to_space.alloc(lisp_str);
                     NonNull::from(alloc)
@@ -87,11 +93,29 @@ impl Deref for LispString {
 
 impl LispString {
     pub(in crate::core) unsafe fn new(string: *mut str, constant: bool) -> Self {
-        Self(GcHeap::new(LispStringInner(Cell::new(string)), constant))
+        let inner = LispStringInner { string: Cell::new(string), read_only: Cell::new(false) };
+        Self(GcHeap::new(inner, constant))
     }
 
     pub(crate) fn inner(&self) -> &str {
-        unsafe { &*self.0 .0.get() }
+        unsafe { &*self.0.string.get() }
+    }
+
+    /// Prevent any further mutation of this string.
+    pub(in crate::core) fn set_read_only(&self) {
+        self.0.read_only.set(true);
+    }
+
+    pub(crate) fn is_read_only(&self) -> bool {
+        self.0.read_only.get() || matches!(self.0.allocation_state(), AllocState::Global)
+    }
+
+    pub(in crate::core) fn forwarding_ptr(&self) -> Option<NonNull<u8>> {
+        match self.0.allocation_state() {
+            AllocState::Forwarded(f) => Some(f),
+            AllocState::Global => panic!("global string allocation found in local heap"),
+            AllocState::Unmoved => None,
+        }
     }
 }
 
@@ -100,11 +124,45 @@ impl LispString {
         self.chars().count()
     }
 
-    pub(crate) fn clear(&self) {
-        let inner_mut_str = unsafe { &mut *self.0 .0.get() };
+    /// Replace the char at `idx` with `new`. If the two chars have a different
+    /// utf8 width the string is reallocated in `bk`. Panics if `idx` is out of
+    /// range.
+    pub(crate) fn set_char<const C: bool>(
+        &self,
+        idx: usize,
+        new: char,
+        bk: &Block<C>,
+    ) -> Result<()> {
+        if self.is_read_only() {
+            bail!("Attempt to mutate constant String");
+        }
+        let (start, old) = self.char_indices().nth(idx).expect("string index out of range");
+        let end = start + old.len_utf8();
+        if old.len_utf8() == new.len_utf8() {
+            let inner_mut_str = unsafe { &mut *self.0.string.get() };
+            new.encode_utf8(unsafe { &mut inner_mut_str.as_bytes_mut()[start..end] });
+        } else {
+            let string = self.inner();
+            let mut buf = [0; 4];
+            let mut copy = GcString::with_capacity_in(string.len() - end + start + 4, &bk.objects);
+            copy.push_str(&string[..start]);
+            copy.push_str(new.encode_utf8(&mut buf));
+            copy.push_str(&string[end..]);
+            self.0.string.set(copy.as_mut_str());
+            std::mem::forget(copy);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn clear(&self) -> Result<()> {
+        if self.is_read_only() {
+            bail!("Attempt to mutate constant String");
+        }
+        let inner_mut_str = unsafe { &mut *self.0.string.get() };
         for byte in unsafe { inner_mut_str.as_bytes_mut().iter_mut() } {
             *byte = b'\0';
         }
+        Ok(())
     }
 }
 
@@ -25,6 +25,9 @@ struct SymbolCellData {
     special: AtomicBool,
 }
 
+/// The name of a symbol never changes after it is created. Uninterned names are
+/// owned by the symbol's block, so the cell is only written when the collector
+/// moves those bytes to a new block.
 #[derive(Debug)]
 enum SymbolName {
     Interned(&'static str),
@@ -135,8 +138,10 @@ impl<'a> GcMoveable for Symbol<'a> {
 impl Trace for SymbolCell {
     fn trace(&self, state: &mut GcState) {
         if let SymbolName::Uninterned(name) = &self.0.name {
-            let new = state.to_space.alloc_str(name.get());
+            let old = name.get();
+            let new = state.to_space.alloc_str(old);
             let new = unsafe { std::mem::transmute::<&str, &'static str>(new) };
+            debug_assert_eq!(old, new, "symbol name changed while moving it");
             name.set(new);
         }
         // The function cell of the symbol is always cloned in the global symbol
@@ -150,6 +155,35 @@ impl fmt::Display for Symbol<'_> {
     }
 }
 
+impl Symbol<'_> {
+    /// Print the symbol the way `prin1` does, escaping any characters that
+    /// would stop the name from reading back as the same symbol.
+    pub(in crate::core) fn fmt_escaped(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use fmt::Write as _;
+        let name = self.name();
+        if name.is_empty() {
+            return f.write_str("##");
+        }
+        // A symbol that would otherwise be read back as a number needs a
+        // leading escape
+        if crate::reader::parse_number(name).is_some() {
+            f.write_char('\\')?;
+        }
+        for (i, c) in name.chars().enumerate() {
+            let needs_escape = match c {
+                '"' | '\\' | '\'' | ';' | '#' | '(' | ')' | ',' | '`' | '[' | ']' => true,
+                '?' => i == 0,
+                c => c.is_whitespace() || c.is_control(),
+            };
+            if needs_escape {
+                f.write_char('\\')?;
+            }
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Debug for Symbol<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.name())
@@ -293,10 +327,6 @@ impl SymbolCell {
         }
     }
 
-    pub(crate) fn interned(&self) -> bool {
-        matches!(self.0.name, SymbolName::Interned(_))
-    }
-
     #[inline(always)]
     /// Check if the symbol is constant like nil, t, or :keyword
     pub(crate) fn is_const(&self) -> bool {
@@ -390,3 +420,86 @@ impl UninternedSymbolMap {
         self.map.borrow_mut().clear();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::gc::{Context, RootSet};
+    use crate::core::object::ObjectType;
+    use rune_core::macros::root;
+
+    #[test]
+    fn test_display() {
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        let sym = Symbol::new_uninterned("foo bar", cx);
+        assert_eq!(sym.to_string(), "foo bar");
+        let prin1 = |name| cx.add(Symbol::new_uninterned(name, cx)).to_string();
+        assert_eq!(prin1("foo"), "foo");
+        assert_eq!(prin1("foo-bar?"), "foo-bar?");
+        assert_eq!(prin1("foo bar"), "foo\\ bar");
+        assert_eq!(prin1("(a)"), "\\(a\\)");
+        assert_eq!(prin1("?a"), "\\?a");
+        assert_eq!(prin1(""), "##");
+        // only names the reader parses as numbers get a leading escape
+        assert_eq!(prin1("1"), "\\1");
+        assert_eq!(prin1("-1.5"), "\\-1.5");
+        assert_eq!(prin1("1e5"), "\\1e5");
+        assert_eq!(prin1("1.0e+INF"), "\\1.0e+INF");
+        assert_eq!(prin1("1+"), "1+");
+        assert_eq!(prin1("3.0.0"), "3.0.0");
+        assert_eq!(prin1("inf"), "inf");
+        assert_eq!(prin1("nan"), "nan");
+        assert_eq!(prin1("infinity"), "infinity");
+    }
+
+    #[test]
+    fn test_uninterned_collected() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        let kept = cx.add(Symbol::new_uninterned("kept", cx));
+        root!(kept, cx);
+        cx.garbage_collect(true);
+        let baseline = cx.block.objects.allocated_bytes();
+        for i in 0..1000 {
+            Symbol::new_uninterned(&format!("uninterned-symbol-{i}"), cx);
+        }
+        assert!(cx.block.objects.allocated_bytes() > baseline);
+        cx.garbage_collect(true);
+        assert_eq!(cx.block.objects.allocated_bytes(), baseline);
+        let ObjectType::Symbol(kept) = kept.bind(cx).untag() else { unreachable!() };
+        assert_eq!(kept.name(), "kept");
+    }
+
+    #[test]
+    fn test_uninterned_owns_name() {
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        let mut name = String::from("owned");
+        let sym = Symbol::new_uninterned(&name, cx);
+        name.make_ascii_uppercase();
+        assert_eq!(sym.name(), "owned");
+        assert!(!std::ptr::eq(sym.name().as_ptr(), name.as_ptr()));
+    }
+
+    #[test]
+    fn test_function_survives_collection() {
+        use crate::core::env::{intern, sym};
+        use crate::core::object::FunctionType;
+        use rune_core::macros::list;
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        sym::init_symbols();
+        let name = intern("gc-function-test", cx);
+        let string = cx.add("survivor".to_owned());
+        let func = list![sym::LAMBDA, sym::NIL, string; cx];
+        crate::data::fset(name, func).unwrap();
+        // only the interned symbol references the function
+        cx.garbage_collect(true);
+        cx.garbage_collect(true);
+        let name = intern("gc-function-test", cx);
+        let func = name.func(cx).unwrap();
+        let FunctionType::Cons(func) = func.untag() else { unreachable!() };
+        assert_eq!(func.elements().nth(2).unwrap().unwrap(), "survivor");
+    }
+}
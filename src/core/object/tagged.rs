@@ -749,8 +749,11 @@ impl<'a> TaggedPtr for NumberType<'a> {
     }
 }
 
-const MAX_FIXNUM: i64 = i64::MAX >> 8;
-const MIN_FIXNUM: i64 = i64::MIN >> 8;
+/// The largest integer that can be stored in an object. Larger values are
+/// clamped to this when tagged.
+pub(crate) const MAX_FIXNUM: i64 = i64::MAX >> 8;
+/// The smallest integer that can be stored in an object.
+pub(crate) const MIN_FIXNUM: i64 = i64::MIN >> 8;
 
 impl TaggedPtr for i64 {
     type Ptr = i64;
@@ -990,6 +993,30 @@ impl<'old, 'new> WithLifetime<'new> for NumberType<'old> {
     }
 }
 
+// Equality and hashing follow `eql`: integers compare by value and floats by
+// their bit pattern, so `1` and `1.0` are distinct keys while `0.0` and `-0.0`
+// are too.
+impl PartialEq for NumberType<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (NumberType::Int(a), NumberType::Int(b)) => a == b,
+            (NumberType::Float(a), NumberType::Float(b)) => a.to_bits() == b.to_bits(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for NumberType<'_> {}
+
+impl Hash for NumberType<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            NumberType::Int(x) => (Tag::Int as u8, *x).hash(state),
+            NumberType::Float(x) => (Tag::Float as u8, x.to_bits()).hash(state),
+        }
+    }
+}
+
 // List
 #[derive(Copy, Clone)]
 #[repr(u8)]
@@ -1478,6 +1505,20 @@ impl GcMoveable for List<'_> {
     }
 }
 
+impl GcMoveable for Number<'_> {
+    type Value = Self;
+
+    fn move_value(&self, to_space: &bumpalo::Bump) -> Option<(Self::Value, bool)> {
+        let data = match self.untag() {
+            NumberType::Float(x) => cast_pair(x.move_value(to_space)?),
+            NumberType::Int(_) => return None,
+        };
+
+        let tag = self.get_tag();
+        unsafe { Some((Number::from_ptr(data.0, tag), data.1)) }
+    }
+}
+
 fn cast_pair<T>((ptr, moved): (NonNull<T>, bool)) -> (*const u8, bool) {
     (ptr.as_ptr().cast::<u8>(), moved)
 }
@@ -1635,7 +1676,7 @@ impl ObjectType<'_> {
             ObjectType::HashTable(x) => x.display_walk(f, seen),
             ObjectType::String(x) => write!(f, "\"{x}\""),
             ObjectType::ByteString(x) => write!(f, "\"{x}\""),
-            ObjectType::Symbol(x) => D::fmt(x, f),
+            ObjectType::Symbol(x) => x.fmt_escaped(f),
             ObjectType::ByteFn(x) => D::fmt(x, f),
             ObjectType::SubrFn(x) => D::fmt(x, f),
             ObjectType::Float(x) => D::fmt(x, f),
@@ -1672,4 +1713,29 @@ mod test {
         cons.as_cons().set_car(cons).unwrap();
         assert_eq!(format!("{cons}"), "(#0 . #0)");
     }
+
+    #[test]
+    // The only interior mutability in a float is its GC header, which is not
+    // part of the hash.
+    #[expect(clippy::mutable_key_type)]
+    fn test_number_hash() {
+        use super::{Number, NumberType};
+        use crate::core::object::Object;
+        use rune_core::hashmap::HashSet;
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        fn num(x: Object) -> NumberType {
+            Number::try_from(x).unwrap().untag()
+        }
+        let mut set = HashSet::default();
+        assert!(set.insert(num(cx.add(1))));
+        assert!(set.insert(num(cx.add(1.0))));
+        assert!(!set.insert(num(cx.add(1))));
+        assert!(!set.insert(num(cx.add(1.0))));
+        assert!(set.insert(num(cx.add(-0.0))));
+        assert!(set.insert(num(cx.add(f64::NAN))));
+        assert!(!set.insert(num(cx.add(f64::NAN))));
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(&NumberType::Int(1)));
+    }
 }
@@ -5,10 +5,11 @@ use crate::core::{
     error::{Type, TypeError},
     gc::{Context, Rt},
     object::{
-        IntoObject, List, ListType, Number, Object, ObjectType, SubrFn, Symbol, WithLifetime, NIL,
+        IntoObject, List, ListType, Number, NumberType, Object, ObjectType, SubrFn, Symbol,
+        WithLifetime, NIL,
     },
 };
-use anyhow::{anyhow, Result};
+use anyhow::{bail, Result};
 use rune_core::{hashmap::HashSet, macros::list};
 use rune_macros::defun;
 use std::sync::LazyLock;
@@ -69,15 +70,43 @@ pub(crate) fn get<'ob>(
     env: &Rt<Env>,
     cx: &'ob Context,
 ) -> Object<'ob> {
-    match env.props.get(symbol) {
-        Some(plist) => match plist.iter().find(|x| x.0 == propname) {
-            Some(element) => cx.bind(element.1.bind(cx)),
-            None => NIL,
-        },
+    match env.get_prop(symbol, propname) {
+        Some(value) => value.bind(cx),
         None => NIL,
     }
 }
 
+#[defun]
+pub(crate) fn symbol_plist<'ob>(symbol: Symbol, env: &Rt<Env>, cx: &'ob Context) -> Object<'ob> {
+    let Some(plist) = env.props.get(symbol) else { return NIL };
+    let mut elements = Vec::with_capacity(plist.len() * 2);
+    for prop in plist.iter() {
+        elements.push(prop.0.bind(cx).into());
+        elements.push(prop.1.bind(cx));
+    }
+    crate::fns::slice_into_list(&elements, None, cx)
+}
+
+#[defun]
+pub(crate) fn setplist<'ob>(
+    symbol: Symbol,
+    newplist: List<'ob>,
+    env: &mut Rt<Env>,
+) -> Result<Object<'ob>> {
+    let mut props = Vec::new();
+    let mut iter = newplist.elements();
+    while let Some(propname) = iter.next() {
+        let propname: Symbol = propname?.try_into()?;
+        let Some(value) = iter.next() else { bail!("Odd length property list: {newplist}") };
+        props.push((propname, value?));
+    }
+    env.props.remove(symbol);
+    for (propname, value) in props {
+        env.set_prop(symbol, propname, value);
+    }
+    Ok(newplist.into())
+}
+
 #[defun]
 pub(crate) fn local_variable_if_set_p(_sym: Symbol) -> bool {
     // TODO: Implement buffer locals
@@ -91,7 +120,7 @@ pub(crate) fn default_value<'ob>(
     cx: &'ob Context,
 ) -> Result<Object<'ob>> {
     // TODO: Implement buffer locals
-    symbol_value(symbol, env, cx).ok_or_else(|| anyhow!("Void variable: {symbol}"))
+    symbol_value(symbol, env, cx).ok_or_else(|| LispError::void_variable(symbol, cx).into())
 }
 
 #[defun]
@@ -227,6 +256,32 @@ pub(crate) fn floatp(object: Object) -> bool {
     matches!(object.untag(), ObjectType::Float(_))
 }
 
+#[defun]
+fn cl_evenp(integer: i64) -> bool {
+    integer % 2 == 0
+}
+
+#[defun]
+fn cl_oddp(integer: i64) -> bool {
+    integer % 2 != 0
+}
+
+#[defun]
+fn cl_plusp(number: Number) -> bool {
+    match number.untag() {
+        NumberType::Int(x) => x > 0,
+        NumberType::Float(x) => **x > 0.0,
+    }
+}
+
+#[defun]
+fn cl_minusp(number: Number) -> bool {
+    match number.untag() {
+        NumberType::Int(x) => x < 0,
+        NumberType::Float(x) => **x < 0.0,
+    }
+}
+
 #[defun]
 pub(crate) fn atom(object: Object) -> bool {
     !consp(object)
@@ -267,6 +322,11 @@ fn string_to_number<'ob>(string: &str, base: Option<i64>, cx: &'ob Context) -> N
     }
 }
 
+#[defun]
+fn number_to_string(number: Number) -> String {
+    number.to_string()
+}
+
 #[defun]
 pub(crate) fn defvar<'ob>(
     symbol: Symbol,
@@ -275,12 +335,15 @@ pub(crate) fn defvar<'ob>(
     env: &mut Rt<Env>,
 ) -> Result<Object<'ob>> {
     let value = initvalue.unwrap_or_default();
+    symbol.make_special();
     set(symbol, value, env)
 }
 
 #[defun]
 pub(crate) fn make_variable_buffer_local(variable: Symbol) -> Symbol {
-    // TODO: Implement
+    // TODO: Implement buffer local values. For now we only mark the variable as
+    // special, which is also what Emacs does.
+    variable.make_special();
     variable
 }
 
@@ -313,71 +376,57 @@ pub(crate) fn aset<'ob>(
     array: Object<'ob>,
     idx: usize,
     newlet: Object<'ob>,
+    cx: &'ob Context,
 ) -> Result<Object<'ob>> {
+    let out_of_range = || LispError::args_out_of_range(&[array, idx.into()], cx).into();
     match array.untag() {
         ObjectType::Vec(vec) => {
             let vec = vec.try_mut()?;
-            if idx < vec.len() {
-                vec[idx].set(newlet);
-                Ok(newlet)
-            } else {
-                let len = vec.len();
-                Err(anyhow!("index {idx} is out of bounds. Length was {len}"))
-            }
+            let Some(elem) = vec.get(idx) else { return Err(out_of_range()) };
+            elem.set(newlet);
+            Ok(newlet)
         }
         ObjectType::Record(vec) => {
             let vec = vec.try_mut()?;
-            if idx < vec.len() {
-                vec[idx].set(newlet);
-                Ok(newlet)
-            } else {
-                let len = vec.len();
-                Err(anyhow!("index {idx} is out of bounds. Length was {len}"))
-            }
+            let Some(elem) = vec.get(idx) else { return Err(out_of_range()) };
+            elem.set(newlet);
+            Ok(newlet)
         }
         ObjectType::CharTable(table) => {
             table.set(idx, newlet);
             Ok(newlet)
         }
+        ObjectType::String(string) => {
+            if idx >= string.len() {
+                return Err(out_of_range());
+            }
+            let chr = match newlet.untag() {
+                ObjectType::Int(x) => u32::try_from(x).ok().and_then(char::from_u32),
+                _ => None,
+            };
+            let Some(chr) = chr else { return Err(TypeError::new(Type::Char, newlet).into()) };
+            string.set_char(idx, chr, cx)?;
+            Ok(newlet)
+        }
         x => Err(TypeError::new(Type::Sequence, x).into()),
     }
 }
 
 #[defun]
 pub(crate) fn aref<'ob>(array: Object<'ob>, idx: usize, cx: &'ob Context) -> Result<Object<'ob>> {
+    let out_of_range = || LispError::args_out_of_range(&[array, idx.into()], cx).into();
     match array.untag() {
-        ObjectType::Vec(vec) => match vec.get(idx) {
-            Some(x) => Ok(x.get()),
-            None => {
-                let len = vec.len();
-                Err(anyhow!("index {idx} is out of bounds. Length was {len}"))
-            }
-        },
-        ObjectType::Record(vec) => match vec.get(idx) {
-            Some(x) => Ok(x.get()),
-            None => {
-                let len = vec.len();
-                Err(anyhow!("index {idx} is out of bounds. Length was {len}"))
-            }
-        },
+        ObjectType::Vec(vec) => vec.get(idx).map(|x| x.get()).ok_or_else(out_of_range),
+        ObjectType::Record(vec) => vec.get(idx).map(|x| x.get()).ok_or_else(out_of_range),
         ObjectType::String(string) => match string.chars().nth(idx) {
             Some(x) => Ok((i64::from(x as u32)).into()),
-            None => {
-                let len = string.len();
-                Err(anyhow!("index {idx} is out of bounds. Length was {len}"))
-            }
+            None => Err(out_of_range()),
         },
         ObjectType::ByteString(string) => match string.get(idx) {
             Some(x) => Ok((i64::from(*x)).into()),
-            None => {
-                let len = string.len();
-                Err(anyhow!("index {idx} is out of bounds. Length was {len}"))
-            }
-        },
-        ObjectType::ByteFn(fun) => match fun.index(idx, cx) {
-            Some(x) => Ok(x),
-            None => Err(anyhow!("index {idx} is out of bounds")),
+            None => Err(out_of_range()),
         },
+        ObjectType::ByteFn(fun) => fun.index(idx, cx).ok_or_else(out_of_range),
         ObjectType::CharTable(chartable) => Ok(chartable.get(idx)),
         x => Err(TypeError::new(Type::Sequence, x).into()),
     }
@@ -401,6 +450,17 @@ fn type_of(object: Object) -> Object {
     }
 }
 
+#[defun]
+fn cl_typep(object: Object, ty: Symbol) -> bool {
+    match ty {
+        sym::TRUE => true,
+        sym::NULL => object.is_nil(),
+        sym::LIST => object.is_nil() || matches!(object.untag(), ObjectType::Cons(_)),
+        sym::NUMBER => matches!(object.untag(), ObjectType::Int(_) | ObjectType::Float(_)),
+        _ => type_of(object) == ty,
+    }
+}
+
 #[defun]
 pub(crate) fn indirect_function<'ob>(object: Object<'ob>, cx: &'ob Context) -> Object<'ob> {
     match object.untag() {
@@ -498,6 +558,11 @@ impl std::fmt::Display for LispError {
 }
 
 defsym!(WRONG_NUMBER_OF_ARGUMENTS);
+defsym!(ARGS_OUT_OF_RANGE);
+defsym!(ARITH_ERROR);
+defsym!(OVERFLOW_ERROR);
+defsym!(NO_CATCH);
+defsym!(CIRCULAR_LIST);
 impl LispError {
     pub(crate) fn new(message: &Cons) -> Self {
         Self { message: unsafe { message.with_lifetime() } }
@@ -517,6 +582,29 @@ impl LispError {
         let list = list![sym::WRONG_NUMBER_OF_ARGUMENTS, func, expected, actual; cx];
         Self::new(list.try_into().unwrap())
     }
+
+    pub(crate) fn arith_error(cx: &Context) -> Self {
+        Self::new(Cons::new1(sym::ARITH_ERROR, cx))
+    }
+
+    pub(crate) fn overflow_error(cx: &Context) -> Self {
+        Self::new(Cons::new1(sym::OVERFLOW_ERROR, cx))
+    }
+
+    pub(crate) fn args_out_of_range(args: &[Object], cx: &Context) -> Self {
+        let args = crate::fns::slice_into_list(args, None, cx);
+        Self::new(Cons::new(sym::ARGS_OUT_OF_RANGE, args, cx))
+    }
+
+    pub(crate) fn void_variable(symbol: Symbol, cx: &Context) -> Self {
+        let list = list![sym::VOID_VARIABLE, symbol; cx];
+        Self::new(list.try_into().unwrap())
+    }
+
+    pub(crate) fn no_catch(tag: Object, value: Object, cx: &Context) -> Self {
+        let list = list![sym::NO_CATCH, tag, value; cx];
+        Self::new(list.try_into().unwrap())
+    }
 }
 
 unsafe impl Send for LispError {}
@@ -540,10 +628,140 @@ mod test {
     fn test_functionp() {
         assert_lisp("(functionp '(lambda nil))", "t");
     }
+
+    #[test]
+    fn test_vector_access() {
+        assert_lisp("(aref (make-vector 3 0) 1)", "0");
+        assert_lisp("(make-vector 0 'x)", "[]");
+        assert_lisp("(vector 1 'a \"b\")", "[1 a \"b\"]");
+        assert_lisp("(aref (vector 1 2 3) 2)", "3");
+        assert_lisp("(let ((v (make-vector 3 nil))) (aset v 1 'x) (aref v 1))", "x");
+        assert_lisp("(let ((v (vector 1 2))) (list (aset v 0 5) v))", "(5 [5 2])");
+        assert_lisp(
+            "(condition-case err (aref (make-vector 3 0) 3) (error err))",
+            "(args-out-of-range [0 0 0] 3)",
+        );
+        assert_lisp(
+            "(condition-case err (aset (vector) 0 1) (error err))",
+            "(args-out-of-range [] 0)",
+        );
+        assert_lisp("(condition-case err (aref \"ab\" 2) (error (car err)))", "args-out-of-range");
+    }
+
+    #[test]
+    fn test_string_access() {
+        assert_lisp("(aref \"abc\" 1)", "98");
+        assert_lisp("(aref \"aλc\" 2)", "99");
+        assert_lisp("(let ((s (concat \"abc\"))) (aset s 1 ?x) s)", "\"axc\"");
+        // multibyte replacement reallocates the string
+        assert_lisp("(let ((s (concat \"abc\"))) (aset s 1 ?λ) s)", "\"aλc\"");
+        assert_lisp(
+            "(let ((s (concat \"aλc\"))) (aset s 1 ?b) (list s (aref s 2)))",
+            "(\"abc\" 99)",
+        );
+        assert_lisp(
+            "(condition-case err (aset (concat \"ab\") 2 ?c) (error (car err)))",
+            "args-out-of-range",
+        );
+    }
+
+    #[test]
+    fn test_symbol_props() {
+        assert_lisp(
+            "(progn (put 'sym 'a 1) (put 'sym 'b 2) (list (get 'sym 'a) (get 'sym 'b)))",
+            "(1 2)",
+        );
+        assert_lisp("(progn (put 'sym 'a 1) (put 'sym 'a 3) (get 'sym 'a))", "3");
+        assert_lisp("(get 'sym 'missing)", "nil");
+        // setting a property to nil leaves it indistinguishable from an unset one
+        assert_lisp("(progn (put 'sym 'a 1) (put 'sym 'a nil) (get 'sym 'a))", "nil");
+    }
+
+    #[test]
+    fn test_symbol_plist() {
+        assert_lisp("(symbol-plist 'sym)", "nil");
+        assert_lisp("(progn (put 'sym 'a 1) (put 'sym 'b 2) (symbol-plist 'sym))", "(a 1 b 2)");
+        assert_lisp("(progn (setplist 'sym '(x 1 y \"z\")) (symbol-plist 'sym))", "(x 1 y \"z\")");
+        assert_lisp(
+            "(progn (put 'sym 'a 1) (setplist 'sym '(b 2)) (list (get 'sym 'a) (get 'sym 'b)))",
+            "(nil 2)",
+        );
+        assert_lisp("(progn (put 'sym 'a 1) (setplist 'sym nil) (symbol-plist 'sym))", "nil");
+        assert_lisp("(condition-case nil (setplist 'sym '(a 1 b)) (error 'err))", "err");
+    }
+
+    #[test]
+    fn test_null() {
+        assert_lisp("(null nil)", "t");
+        assert_lisp("(null 5)", "nil");
+        assert_lisp("(null '(nil))", "nil");
+        // Predicates return the canonical t
+        assert_lisp("(eq (null nil) t)", "t");
+        assert_lisp("(eq (consp '(1)) 't)", "t");
+        assert_lisp("(eq (stringp \"a\") (intern \"t\"))", "t");
+    }
+
+    #[test]
+    fn test_type_of() {
+        assert_lisp("(type-of 1)", "integer");
+        assert_lisp("(type-of 1.0)", "float");
+        assert_lisp("(type-of 'x)", "symbol");
+        assert_lisp("(type-of nil)", "symbol");
+        assert_lisp("(type-of \"a\")", "string");
+        assert_lisp("(type-of '(1))", "cons");
+        assert_lisp("(type-of [1])", "vector");
+        assert_lisp("(type-of (make-hash-table))", "hash-table");
+    }
+
+    #[test]
+    fn test_number_predicates() {
+        assert_lisp("(cl-evenp 4)", "t");
+        assert_lisp("(cl-oddp 4)", "nil");
+        assert_lisp("(list (cl-evenp -3) (cl-oddp -3) (cl-evenp 0))", "(nil t t)");
+        assert_lisp("(condition-case nil (cl-evenp 4.0) (error 'err))", "err");
+        assert_lisp("(condition-case nil (cl-oddp 1.0) (error 'err))", "err");
+        assert_lisp("(cl-plusp 0)", "nil");
+        assert_lisp("(list (cl-plusp 2) (cl-plusp 0.5) (cl-plusp -1))", "(t t nil)");
+        assert_lisp("(list (cl-minusp -2) (cl-minusp -0.5) (cl-minusp 0))", "(t t nil)");
+        assert_lisp("(list (cl-plusp 0.0) (cl-minusp 0.0) (cl-minusp -0.0))", "(nil nil nil)");
+    }
+
+    #[test]
+    fn test_cl_typep() {
+        assert_lisp("(cl-typep 1 'integer)", "t");
+        assert_lisp("(cl-typep 1 'number)", "t");
+        assert_lisp("(cl-typep 1 'string)", "nil");
+        assert_lisp(
+            "(list (cl-typep 1.5 'float) (cl-typep 1.5 'number) (cl-typep 1.5 'integer))",
+            "(t t nil)",
+        );
+        assert_lisp(
+            "(list (cl-typep \"a\" 'string) (cl-typep 'a 'symbol) (cl-typep '(1) 'cons))",
+            "(t t t)",
+        );
+        assert_lisp("(list (cl-typep nil 'null) (cl-typep nil 'list) (cl-typep nil 'symbol) (cl-typep nil 'cons))", "(t t t nil)");
+        assert_lisp(
+            "(list (cl-typep '(1) 'list) (cl-typep '(1) 'null) (cl-typep 1 'list))",
+            "(t nil nil)",
+        );
+        assert_lisp("(list (cl-typep 1 t) (cl-typep nil t) (cl-typep [1] 'vector))", "(t t t)");
+    }
+
+    #[test]
+    fn test_type_predicates() {
+        // nil is a symbol, a list, and null all at once
+        assert_lisp("(list (null nil) (listp nil) (symbolp nil) (atom nil))", "(t t t t)");
+        assert_lisp("(list (consp nil) (stringp nil))", "(nil nil)");
+        assert_lisp("(list (symbolp t) (null t) (listp t))", "(t nil nil)");
+        assert_lisp("(list (consp '(1)) (listp '(1)) (atom '(1)) (null '(1)))", "(t t nil nil)");
+        assert_lisp("(list (stringp \"a\") (atom \"a\") (symbolp \"a\"))", "(t t nil)");
+        assert_lisp("(list (symbolp 'a) (atom 1) (listp 1) (null 0))", "(t t nil nil)");
+    }
 }
 
 defsym!(MANY);
 defsym!(INTEGER);
+defsym!(NUMBER);
 defsym!(SYMBOL);
 defsym!(COMPILED_FUNCTION);
 defsym!(HASH_TABLE);
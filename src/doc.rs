@@ -0,0 +1,92 @@
+//! Documentation strings.
+use crate::core::{
+    env::{sym, Env},
+    error::{Type, TypeError},
+    gc::{Context, Rt},
+    object::{Object, ObjectType, Symbol, NIL},
+};
+use anyhow::{bail, Result};
+use fallible_iterator::FallibleIterator;
+use rune_macros::defun;
+
+fn string_or_nil(obj: Object) -> Object {
+    match obj.untag() {
+        ObjectType::String(_) => obj,
+        // TODO: Docstrings that live in a file are not supported yet
+        _ => NIL,
+    }
+}
+
+#[defun]
+fn documentation<'ob>(
+    function: Object<'ob>,
+    _raw: Option<Object>,
+    cx: &'ob Context,
+) -> Result<Object<'ob>> {
+    match function.untag() {
+        ObjectType::Symbol(sym) => match sym.follow_indirect(cx) {
+            Some(func) => documentation(func.into(), None, cx),
+            None => bail!("Symbol's function definition is void: {sym}"),
+        },
+        ObjectType::ByteFn(func) => Ok(func.doc().map_or(NIL, |x| string_or_nil(cx.bind(x)))),
+        ObjectType::SubrFn(_) => Ok(NIL),
+        ObjectType::Cons(func) => {
+            let doc_pos = match func.car().untag() {
+                ObjectType::Symbol(sym::CLOSURE) => 3,
+                ObjectType::Symbol(sym::LAMBDA) => 2,
+                other => bail!(TypeError::new(Type::Func, other)),
+            };
+            let doc = func.elements().fallible().nth(doc_pos)?;
+            Ok(doc.map_or(NIL, string_or_nil))
+        }
+        _ => Err(TypeError::new(Type::Func, function).into()),
+    }
+}
+
+#[defun]
+fn documentation_property<'ob>(
+    symbol: Symbol,
+    prop: Symbol,
+    _raw: Option<Object>,
+    env: &Rt<Env>,
+    cx: &'ob Context,
+) -> Object<'ob> {
+    string_or_nil(crate::data::get(symbol, prop, env, cx))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interpreter::assert_lisp;
+
+    #[test]
+    fn test_documentation() {
+        assert_lisp("(documentation #'(lambda (x) \"Double X.\" (* x 2)))", "\"Double X.\"");
+        assert_lisp("(documentation #'(lambda (x) (* x 2)))", "nil");
+        assert_lisp(
+            "(progn (fset 'doc-test #'(lambda () \"Some docs.\" nil)) (documentation 'doc-test))",
+            "\"Some docs.\"",
+        );
+        assert_lisp("(documentation 'car)", "nil");
+        assert_lisp(
+            "(documentation (make-byte-code 0 (unibyte-string 192 135) [1] 1 \"Return 1.\"))",
+            "\"Return 1.\"",
+        );
+        assert_lisp(
+            "(aref (make-byte-code 0 (unibyte-string 192 135) [1] 1 \"Return 1.\") 4)",
+            "\"Return 1.\"",
+        );
+    }
+
+    #[test]
+    fn test_documentation_property() {
+        assert_lisp(
+            "(progn (defvar doc-test-var 1 \"A variable.\") (documentation-property 'doc-test-var 'variable-documentation))",
+            "\"A variable.\"",
+        );
+        assert_lisp(
+            "(progn (put 'doc-test-var2 'variable-documentation \"From the plist.\") (documentation-property 'doc-test-var2 'variable-documentation))",
+            "\"From the plist.\"",
+        );
+        assert_lisp("(documentation-property 'doc-test-var3 'variable-documentation)", "nil");
+    }
+}
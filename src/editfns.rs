@@ -1,12 +1,12 @@
 //! Buffer editing utilities.
 use crate::core::{
-    env::{ArgSlice, Env},
+    env::{sym, ArgSlice, Env},
     gc::{Context, Rt},
     object::{Object, ObjectType},
 };
-use anyhow::{bail, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use rune_macros::defun;
-use std::{fmt::Write as _, io::Write};
+use std::io::Write;
 
 #[defun]
 fn message(format_string: &str, args: &[Object]) -> Result<String> {
@@ -39,29 +39,208 @@ fn format(string: &str, objects: &[Object]) -> Result<String> {
     };
     while let Some(start) = remaining.find(&mut is_format_char) {
         result += &remaining[..start];
-        let Some(specifier) = remaining.as_bytes().get(start + 1) else {
+        let Some((spec, len)) = FormatSpec::parse(&remaining[start + 1..]) else {
             bail!("Format string ends in middle of format specifier")
         };
         // "%%" inserts a single "%" in the output
-        if *specifier == b'%' {
+        if spec.conversion == '%' {
             result.push('%');
         } else {
-            // TODO: currently handles all format types the same. Need to check the modifier characters.
             let Some(val) = arguments.next() else {
                 bail!("Not enough arguments for format string")
             };
-            match val.untag() {
-                ObjectType::String(string) => write!(result, "{string}")?,
-                obj => write!(result, "{obj}")?,
-            }
+            spec.write(*val, &mut result)?;
         }
-        remaining = &remaining[start + 2..];
+        remaining = &remaining[start + 1 + len..];
     }
     result += remaining;
     ensure!(arguments.next().is_none(), "Too many arguments for format string");
     Ok(result)
 }
 
+/// A single `%` directive in a format string.
+#[derive(Default)]
+struct FormatSpec {
+    left_align: bool,
+    plus: bool,
+    space: bool,
+    zero_pad: bool,
+    width: usize,
+    precision: Option<usize>,
+    conversion: char,
+}
+
+impl FormatSpec {
+    /// Parse the flags, width, precision and conversion character following a
+    /// `%`. Returns the spec and the number of bytes it occupied.
+    fn parse(spec: &str) -> Option<(Self, usize)> {
+        let mut this = Self::default();
+        let mut chars = spec.char_indices().peekable();
+        while let Some((_, c)) = chars.next_if(|(_, c)| matches!(c, '-' | '+' | ' ' | '0' | '#')) {
+            match c {
+                '-' => this.left_align = true,
+                '+' => this.plus = true,
+                ' ' => this.space = true,
+                '0' => this.zero_pad = true,
+                _ => {}
+            }
+        }
+        while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+            this.width = this.width * 10 + c.to_digit(10).unwrap() as usize;
+        }
+        if chars.next_if(|(_, c)| *c == '.').is_some() {
+            let mut precision = 0;
+            while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                precision = precision * 10 + c.to_digit(10).unwrap() as usize;
+            }
+            this.precision = Some(precision);
+        }
+        let (idx, conversion) = chars.next()?;
+        this.conversion = conversion;
+        Some((this, idx + conversion.len_utf8()))
+    }
+
+    fn write(&self, obj: Object, out: &mut String) -> Result<()> {
+        let mismatch = || anyhow!("Format specifier doesn't match argument type");
+        let number = || match obj.untag() {
+            ObjectType::Int(x) => Ok(x as f64),
+            ObjectType::Float(x) => Ok(**x),
+            _ => Err(mismatch()),
+        };
+        let integer = || match obj.untag() {
+            ObjectType::Int(x) => Ok(x),
+            ObjectType::Float(x) => Ok(**x as i64),
+            _ => Err(mismatch()),
+        };
+        let precision = self.precision.unwrap_or(6);
+        let (text, is_numeric) = match self.conversion {
+            's' | 'S' => {
+                let mut text = match obj.untag() {
+                    ObjectType::String(string) if self.conversion == 's' => {
+                        string.inner().to_owned()
+                    }
+                    ObjectType::Symbol(symbol) if self.conversion == 's' => {
+                        symbol.name().to_owned()
+                    }
+                    _ => format!("{obj}"),
+                };
+                if let Some((idx, _)) = self.precision.and_then(|p| text.char_indices().nth(p)) {
+                    text.truncate(idx);
+                }
+                (text, false)
+            }
+            'c' => {
+                let chr = u32::try_from(integer()?).ok().and_then(char::from_u32);
+                (chr.ok_or_else(mismatch)?.to_string(), false)
+            }
+            'd' | 'i' => {
+                let int = integer()?;
+                (self.signed(int.to_string(), int < 0), true)
+            }
+            'o' | 'x' | 'X' => {
+                // negative numbers get a sign rather than their two's complement
+                let int = integer()?;
+                let abs = int.unsigned_abs();
+                let digits = match self.conversion {
+                    'o' => format!("{abs:o}"),
+                    'x' => format!("{abs:x}"),
+                    _ => format!("{abs:X}"),
+                };
+                let sign = if int < 0 { "-" } else { "" };
+                (self.signed(format!("{sign}{digits}"), int < 0), true)
+            }
+            'f' => {
+                let num = number()?;
+                (self.signed(format!("{num:.precision$}"), num.is_sign_negative()), true)
+            }
+            'e' => {
+                let num = number()?;
+                (self.signed(exponential(num, precision), num.is_sign_negative()), true)
+            }
+            'g' => {
+                let num = number()?;
+                (self.signed(general(num, precision), num.is_sign_negative()), true)
+            }
+            c => bail!("Invalid format operation %{c}"),
+        };
+        self.pad(&text, is_numeric, out);
+        Ok(())
+    }
+
+    /// Add the sign requested by the `+` or ` ` flags to a non-negative number.
+    fn signed(&self, text: String, negative: bool) -> String {
+        if !negative && self.plus {
+            format!("+{text}")
+        } else if !negative && self.space {
+            format!(" {text}")
+        } else {
+            text
+        }
+    }
+
+    fn pad(&self, text: &str, is_numeric: bool, out: &mut String) {
+        let padding = self.width.saturating_sub(text.chars().count());
+        if self.left_align {
+            out.push_str(text);
+            out.extend(std::iter::repeat_n(' ', padding));
+        } else if self.zero_pad && is_numeric {
+            // zeros go between the sign and the digits
+            let (sign, digits) = match text.strip_prefix(['-', '+', ' ']) {
+                Some(digits) => (&text[..1], digits),
+                None => ("", text),
+            };
+            out.push_str(sign);
+            out.extend(std::iter::repeat_n('0', padding));
+            out.push_str(digits);
+        } else {
+            out.extend(std::iter::repeat_n(' ', padding));
+            out.push_str(text);
+        }
+    }
+}
+
+/// Format `num` like C's `%.*e`.
+fn exponential(num: f64, precision: usize) -> String {
+    // Rust prints the exponent as "e3", but C uses "e+03"
+    let text = format!("{num:.precision$e}");
+    let Some((mantissa, exp)) = text.split_once('e') else {
+        // infinity and NaN have no exponent
+        return text;
+    };
+    let (sign, digits) = match exp.strip_prefix('-') {
+        Some(digits) => ('-', digits),
+        None => ('+', exp),
+    };
+    format!("{mantissa}e{sign}{digits:0>2}")
+}
+
+/// Format `num` like C's `%.*g`. This uses `%e` style when the exponent is
+/// less than -4 or at least the precision and `%f` style otherwise, then
+/// removes trailing zeros from the fraction.
+fn general(num: f64, precision: usize) -> String {
+    let precision = precision.max(1);
+    let text = exponential(num, precision - 1);
+    let Some((_, exp)) = text.split_once('e') else {
+        return text;
+    };
+    let exp: i64 = exp.parse().unwrap();
+    let text = if exp < -4 || exp >= precision as i64 {
+        text
+    } else {
+        let decimals = (precision as i64 - 1 - exp) as usize;
+        format!("{num:.decimals$}")
+    };
+    let (mantissa, exp) = match text.find('e') {
+        Some(idx) => text.split_at(idx),
+        None => (&*text, ""),
+    };
+    if !mantissa.contains('.') {
+        return text;
+    }
+    let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+    format!("{mantissa}{exp}")
+}
+
 #[defun]
 fn format_message(string: &str, objects: &[Object]) -> Result<String> {
     let formatted = format(string, objects)?;
@@ -82,6 +261,18 @@ fn char_to_string(chr: char) -> String {
     format!("{chr}")
 }
 
+defvar!(CASE_FOLD_SEARCH, true);
+
+#[defun]
+fn char_equal(c1: char, c2: char, env: &Rt<Env>, cx: &Context) -> bool {
+    if c1 == c2 {
+        return true;
+    }
+    // TODO: case-fold-search should be buffer local and use the buffer case table
+    let fold = env.vars.get(sym::CASE_FOLD_SEARCH).is_some_and(|x| !x.bind(cx).is_nil());
+    fold && c1.to_lowercase().eq(c2.to_lowercase())
+}
+
 #[defun]
 pub(crate) fn insert(args: ArgSlice, env: &mut Rt<Env>, cx: &Context) -> Result<()> {
     let env = &mut **env; // Deref into rooted type so we can split the borrow
@@ -152,6 +343,7 @@ mod test {
     use crate::{
         buffer::{get_buffer_create, set_buffer},
         core::gc::RootSet,
+        interpreter::assert_lisp,
     };
     use rune_core::macros::root;
 
@@ -173,6 +365,56 @@ mod test {
         assert!(format("`%s' %s%s%s", &[0.into(), 1.into(), 2.into(), 3.into()]).is_ok());
     }
 
+    #[test]
+    fn test_format_directives() {
+        assert_lisp(r#"(format "%d + %d = %d" 1 2 3)"#, r#""1 + 2 = 3""#);
+        assert_lisp(r#"(format "%.2f" 3.14159)"#, r#""3.14""#);
+        assert_lisp(r#"(format "%f" 1)"#, r#""1.000000""#);
+        assert_lisp(r#"(format "%d%%" 50)"#, r#""50%""#);
+        assert_lisp(r#"(format "%d" 2.7)"#, r#""2""#);
+        assert_lisp(r#"(format "%s %S" "a" "a")"#, r#""a \"a\"""#);
+        assert_lisp(r#"(format "%.2s" "abc")"#, r#""ab""#);
+        assert_lisp(r#"(format "%s" "a\"b")"#, r#""a\"b""#);
+        assert_lisp(r#"(format "%s %S" 'a\ b 'a\ b)"#, r#""a b a\\ b""#);
+        assert_lisp(r#"(format "%5d|%-5d|%05d" 42 42 -42)"#, r#""   42|42   |-0042""#);
+        assert_lisp(r#"(format "%+d %x %X %o %c" 5 255 255 8 ?a)"#, r#""+5 ff FF 10 a""#);
+        assert_lisp(r#"(format "%x %X %o" -255 -255 -8)"#, r#""-ff -FF -10""#);
+        assert_lisp(r#"(format "%+x|%05x|%-5o|" 255 -255 -8)"#, r#""+ff|-00ff|-10  |""#);
+        assert_lisp(r#"(format "%.2e" 1234.5)"#, r#""1.23e+03""#);
+        assert_lisp(r#"(format "%g" 100000.0)"#, r#""100000""#);
+        assert_lisp(r#"(format "%g" 1000000.0)"#, r#""1e+06""#);
+        assert_lisp(r#"(format "%g" 0.0001)"#, r#""0.0001""#);
+        assert_lisp(r#"(format "%g" 0.00001)"#, r#""1e-05""#);
+        assert_lisp(r#"(format "%g" 3.14159265)"#, r#""3.14159""#);
+        assert_lisp(r#"(format "%g" 2.5)"#, r#""2.5""#);
+        assert_lisp(r#"(format "%g" 0)"#, r#""0""#);
+        assert_lisp(r#"(format "%g" -123456789.0)"#, r#""-1.23457e+08""#);
+        assert_lisp(r#"(format "%.3g" 1234.5)"#, r#""1.23e+03""#);
+        assert_lisp(r#"(format "%.0g" 7.0)"#, r#""7""#);
+        assert_lisp(r#"(format "%8.2g|%-8g|" 0.5 1.5)"#, r#""     0.5|1.5     |""#);
+        assert_lisp(r#"(format "%g %e" 1.0e+INF -1.0e+INF)"#, r#""inf -inf""#);
+        assert_lisp(r#"(condition-case nil (format "%d" "a") (error 'err))"#, "err");
+        assert_lisp(r#"(condition-case nil (format "%d %d" 1) (error 'err))"#, "err");
+    }
+
+    #[test]
+    fn test_char_conversion() {
+        assert_lisp("(char-to-string 97)", r#""a""#);
+        assert_lisp("(char-to-string 955)", r#""λ""#);
+        assert_lisp("(string-to-char (char-to-string 128512))", "128512");
+        assert_lisp(r#"(string-to-char "λx")"#, "955");
+        assert_lisp(r#"(string-to-char "")"#, "0");
+    }
+
+    #[test]
+    fn test_char_equal() {
+        assert_lisp("(char-equal ?a ?a)", "t");
+        assert_lisp("(char-equal ?a ?b)", "nil");
+        assert_lisp("(progn (setq case-fold-search t) (char-equal ?a ?A))", "t");
+        assert_lisp("(progn (setq case-fold-search t) (char-equal ?λ ?Λ))", "t");
+        assert_lisp("(progn (setq case-fold-search nil) (char-equal ?a ?A))", "nil");
+    }
+
     #[test]
     fn test_insert() {
         let roots = &RootSet::default();
@@ -87,6 +87,72 @@ impl EvalError {
     }
 }
 
+impl EvalError {
+    /// The `(ERROR-SYMBOL . DATA)` cons that a condition-case handler receives.
+    /// Returns `None` for a throw, which condition-case never handles.
+    pub(crate) fn condition<'ob>(&self, env: &Rt<Env>, cx: &'ob Context) -> Option<&'ob Cons> {
+        match &self.error {
+            ErrorType::Signal(id) => {
+                let Some((sym, data)) = env.get_exception(*id) else {
+                    unreachable!("Exception not found")
+                };
+                Some(Cons::new(sym, data, cx))
+            }
+            ErrorType::Err(err) => match err.downcast_ref::<LispError>() {
+                Some(lisp_error) => Some(lisp_error.bind(cx)),
+                // The list iterators don't keep the head of the list, so we
+                // can't include it in the error data like Emacs does.
+                None if matches!(err.downcast_ref(), Some(ConsError::CircularList)) => {
+                    Some(Cons::new1(sym::CIRCULAR_LIST, cx))
+                }
+                // TODO: Need to remove the anyhow branch once full errors are
+                // implemented
+                None => Some(Cons::new(sym::ERROR, format!("{err}"), cx)),
+            },
+            ErrorType::Throw(_) => None,
+        }
+    }
+}
+
+/// Check if the CONDITION of a condition-case handler (a symbol or list of
+/// symbols) handles an error signaled with `error_symbol`. The conditions of
+/// an error are the symbol itself, `error`, and anything in its
+/// `error-conditions` property.
+pub(crate) fn handles_error(
+    condition: Object,
+    error_symbol: Object,
+    env: &Rt<Env>,
+    cx: &Context,
+) -> Result<bool> {
+    let parents = match error_symbol.untag() {
+        ObjectType::Symbol(sym) => crate::data::get(sym, sym::ERROR_CONDITIONS, env, cx),
+        _ => NIL,
+    };
+    let matches = |condition: Object| -> Result<bool> {
+        if condition == sym::TRUE || condition == sym::ERROR || condition == error_symbol {
+            return Ok(true);
+        }
+        for parent in parents.as_list()? {
+            if parent? == condition {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    };
+    match condition.untag() {
+        ObjectType::Symbol(_) => matches(condition),
+        ObjectType::Cons(conditions) => {
+            for condition in conditions {
+                if matches(condition?)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        _ => bail!("Invalid condition handler: {condition}"),
+    }
+}
+
 impl From<anyhow::Error> for EvalError {
     fn from(e: anyhow::Error) -> Self {
         Self::new_error(e)
@@ -146,8 +212,7 @@ pub(crate) fn apply<'ob>(
         let end = len - 1;
         env.stack.extend_as_vec_from_within(beg..end);
         for element in last.as_list()? {
-            let e = cx.bind(element?);
-            env.stack.push(e);
+            cx.with_bound(element?, |e| env.stack.push(e));
         }
         let args = env.stack.len() - len;
         let frame = &mut CallFrame::new_with_args(env, args);
@@ -300,6 +365,8 @@ pub(crate) fn macroexpand<'ob>(
     // shadow the macro based on ENVIRONMENT
     let func = match environment {
         Some(env) => match assq(sym.into(), env.bind(cx).try_into()?)?.untag() {
+            // a nil definition means FORM is not a macro call
+            ObjectType::Cons(cons) if cons.cdr().is_nil() => None,
             ObjectType::Cons(cons) => Some(cons.cdr().try_into()?),
             _ => get_macro_func(sym, cx),
         },
@@ -333,6 +400,57 @@ fn get_macro_func<'ob>(name: Symbol, cx: &'ob Context) -> Option<Function<'ob>>
     None
 }
 
+/// Return the `(interactive ...)` form of `cmd`, or nil if it is not a
+/// command.
+#[defun]
+pub(crate) fn interactive_form<'ob>(cmd: Object<'ob>, cx: &'ob Context) -> Result<Object<'ob>> {
+    match cmd.untag() {
+        ObjectType::Symbol(sym) => match sym.follow_indirect(cx) {
+            Some(func) => interactive_form(func.into(), cx),
+            None => Ok(NIL),
+        },
+        ObjectType::ByteFn(func) => match func.interactive() {
+            Some(spec) => Ok(list![sym::INTERACTIVE, spec; cx]),
+            None => Ok(NIL),
+        },
+        ObjectType::Cons(func) => {
+            let body_pos = match func.car().untag() {
+                ObjectType::Symbol(sym::CLOSURE) => 3,
+                ObjectType::Symbol(sym::LAMBDA) => 2,
+                _ => return Ok(NIL),
+            };
+            let mut body = func.elements().fallible().skip(body_pos);
+            let mut form = body.next()?;
+            // skip the docstring
+            if form.is_some_and(|x| matches!(x.untag(), ObjectType::String(_))) {
+                form = body.next()?;
+            }
+            match form {
+                Some(form) if matches!(form.untag(), ObjectType::Cons(x) if x.car() == sym::INTERACTIVE) => {
+                    Ok(form)
+                }
+                _ => Ok(NIL),
+            }
+        }
+        _ => Ok(NIL),
+    }
+}
+
+#[defun]
+fn commandp(
+    function: Object,
+    for_call_interactively: Option<Object>,
+    cx: &Context,
+) -> Result<bool> {
+    match function.untag() {
+        // keyboard macros
+        ObjectType::String(_) | ObjectType::ByteString(_) | ObjectType::Vec(_) => {
+            Ok(for_call_interactively.is_none())
+        }
+        _ => Ok(!interactive_form(function, cx)?.is_nil()),
+    }
+}
+
 #[defun]
 fn func_arity<'ob>(function: Function, cx: &'ob Context) -> Result<&'ob Cons> {
     let from_args = |args: FnArgs| {
@@ -395,6 +513,17 @@ fn special_variable_p(symbol: Symbol) -> bool {
     symbol.is_special()
 }
 
+#[defun]
+fn default_toplevel_value<'ob>(
+    symbol: Symbol,
+    env: &Rt<Env>,
+    cx: &'ob Context,
+) -> Result<Object<'ob>> {
+    // TODO: Implement buffer locals
+    env.toplevel_value(symbol, cx)
+        .ok_or_else(|| LispError::void_variable(symbol, cx).into())
+}
+
 #[defun]
 fn set_default_toplevel_value<'ob>(
     symbol: Symbol,
@@ -417,6 +546,20 @@ fn set_default<'ob>(
 }
 
 impl Rto<Function<'_>> {
+    /// Call this function with `args`. This is a convenience wrapper around
+    /// [`Self::call`] for when the arguments are not already on the stack.
+    pub(crate) fn call_with_args<'ob>(
+        &self,
+        args: &[Rto<Object>],
+        name: Option<&str>,
+        env: &mut Rt<Env>,
+        cx: &'ob mut Context,
+    ) -> EvalResult<'ob> {
+        let frame = &mut CallFrame::new(env);
+        frame.push_arg_slice(Rt::bind_slice(args, cx));
+        self.call(frame, name, cx)
+    }
+
     pub(crate) fn call<'ob>(
         &self,
         frame: &mut CallFrame<'_, '_>,
@@ -471,6 +614,140 @@ pub(crate) fn add_trace(err: anyhow::Error, name: &str, args: &[Rto<Object>]) ->
     }
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{
+        env::intern,
+        gc::{RootSet, Slot},
+    };
+    use crate::interpreter::assert_lisp;
+
+    #[test]
+    fn test_call_with_args() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        sym::init_symbols();
+        root!(env, new(Env), cx);
+        root!(args, new(Vec<Slot<Object>>), cx);
+        args.push(cx.add(3));
+        args.push(cx.add(4));
+
+        let subr = intern("+", cx).func(cx).unwrap();
+        assert!(matches!(subr.untag(), FunctionType::SubrFn(_)));
+        root!(subr, cx);
+        let result = subr.call_with_args(args, None, env, cx).unwrap();
+        assert_eq!(result, 7);
+
+        let closure = crate::reader::read("(closure (t) (x y) (* x y))", cx).unwrap().0;
+        let closure: Function = closure.try_into().unwrap();
+        root!(closure, cx);
+        let result = closure.call_with_args(args, None, env, cx).unwrap();
+        assert_eq!(result, 12);
+
+        args.pop();
+        assert!(closure.call_with_args(args, None, env, cx).is_err());
+    }
+
+    #[test]
+    fn test_funcall() {
+        assert_lisp("(funcall #'+ 1 2 3)", "6");
+        assert_lisp("(funcall '+)", "0");
+        assert_lisp("(funcall #'(lambda (x) (* x 2)) 4)", "8");
+        assert_lisp(
+            "(condition-case err (funcall #'car) (error (car err)))",
+            "wrong-number-of-arguments",
+        );
+    }
+
+    #[test]
+    fn test_special_variable_p() {
+        assert_lisp("(progn (defvar special-test-1 1) (special-variable-p 'special-test-1))", "t");
+        assert_lisp(
+            "(progn (defvar special-test-2 nil) (special-variable-p 'special-test-2))",
+            "t",
+        );
+        assert_lisp("(special-variable-p 'special-test-3)", "nil");
+        assert_lisp("(progn (setq special-test-4 1) (special-variable-p 'special-test-4))", "nil");
+        assert_lisp(
+            "(progn (setq special-test-5 1) (defvar special-test-5 2) (list (special-variable-p 'special-test-5) special-test-5))",
+            "(t 1)",
+        );
+        assert_lisp(
+            "(progn (defconst special-test-6 1) (special-variable-p 'special-test-6))",
+            "t",
+        );
+        assert_lisp(
+            "(progn (make-variable-buffer-local 'special-test-7) (special-variable-p 'special-test-7))",
+            "t",
+        );
+    }
+
+    #[test]
+    fn test_commandp() {
+        assert_lisp("(commandp #'(lambda (n) (interactive \"p\") n))", "t");
+        assert_lisp("(commandp #'(lambda (n) n))", "nil");
+        assert_lisp("(commandp #'(lambda () \"docs\" (interactive) 1))", "t");
+        assert_lisp("(commandp 'car)", "nil");
+        assert_lisp("(list (commandp \"keys\") (commandp \"keys\" t))", "(t nil)");
+        assert_lisp(
+            "(progn (fset 'commandp-test #'(lambda (n) (interactive \"p\") n)) (commandp 'commandp-test))",
+            "t",
+        );
+        let bytecode = "(make-byte-code 0 (unibyte-string 192 135) [1] 1 nil \"p\")";
+        assert_lisp(&format!("(commandp {bytecode})"), "t");
+        assert_lisp(&format!("(funcall {bytecode})"), "1");
+        assert_lisp(&format!("(aref {bytecode} 5)"), "\"p\"");
+    }
+
+    #[test]
+    fn test_interactive_form() {
+        assert_lisp(
+            "(interactive-form #'(lambda (n) \"docs\" (interactive \"p\") n))",
+            "(interactive \"p\")",
+        );
+        assert_lisp("(interactive-form #'(lambda (n) (interactive) n))", "(interactive)");
+        assert_lisp("(interactive-form #'(lambda () \"docs\"))", "nil");
+        assert_lisp("(interactive-form 'car)", "nil");
+        assert_lisp(
+            "(interactive-form (make-byte-code 0 (unibyte-string 192 135) [1] 1 nil \"p\"))",
+            "(interactive \"p\")",
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_lisp("(apply #'+ '(1 2 3))", "6");
+        assert_lisp("(apply #'+ 1 2 '(3 4))", "10");
+        assert_lisp("(apply #'+ nil)", "0");
+        assert_lisp("(apply #'list 1 '(2))", "(1 2)");
+        assert_lisp(
+            "(condition-case err (apply #'car '(1 2)) (error (car err)))",
+            "wrong-number-of-arguments",
+        );
+    }
+
+    #[test]
+    fn test_macroexpand() {
+        let def = "(fset 'mx-inc (cons 'macro #'(lambda (x) (list '1+ x))))";
+        assert_lisp(&format!("(progn {def} (macroexpand '(mx-inc 2)))"), "(1+ 2)");
+        assert_lisp(&format!("(progn {def} (mx-inc 2))"), "3");
+        // expansion repeats until the head is no longer a macro
+        let def2 = "(fset 'mx-inc2 (cons 'macro #'(lambda (x) (list 'mx-inc (list 'mx-inc x)))))";
+        assert_lisp(&format!("(progn {def} {def2} (macroexpand '(mx-inc2 2)))"), "(1+ (mx-inc 2))");
+        assert_lisp(&format!("(progn {def} {def2} (mx-inc2 2))"), "4");
+        // ENVIRONMENT shadows the global definition
+        assert_lisp(
+            &format!(
+                "(progn {def} (macroexpand '(mx-inc 2) (list (cons 'mx-inc #'(lambda (x) x)))))"
+            ),
+            "2",
+        );
+        assert_lisp(&format!("(progn {def} (macroexpand '(mx-inc 2) '((mx-inc))))"), "(mx-inc 2)");
+        assert_lisp("(macroexpand '(car x))", "(car x)");
+    }
+}
+
 defsym!(FUNCTION);
 defsym!(QUOTE);
 defsym!(MACRO);
@@ -486,6 +763,10 @@ defsym!(UNWIND_PROTECT);
 defsym!(SAVE_EXCURSION);
 defsym!(SAVE_CURRENT_BUFFER);
 defsym!(WHILE);
+defsym!(DOLIST);
+defsym!(DOTIMES);
+defsym!(CL_INCF);
+defsym!(CL_DECF);
 defsym!(INLINE);
 defsym!(PROGN);
 defsym!(PROG1);
@@ -496,6 +777,8 @@ defsym!(COND);
 defsym!(LET);
 defsym!(LET_STAR, "let*");
 defsym!(IF);
+defsym!(WHEN);
+defsym!(UNLESS);
 defsym!(AND);
 defsym!(OR);
 defsym!(INTERACTIVE);
@@ -504,6 +787,8 @@ defsym!(THROW);
 defsym!(ERROR);
 defsym!(DEBUG);
 defsym!(VOID_VARIABLE);
+defsym!(ERROR_CONDITIONS);
+defsym!(VARIABLE_DOCUMENTATION);
 
 defvar!(DEBUG_ON_ERROR, false);
 defvar!(INTERNAL_MAKE_INTERPRETED_CLOSURE_FUNCTION);
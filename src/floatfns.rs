@@ -1,12 +1,14 @@
 //! Operations on floats.
 use crate::{
-    arith::NumberValue,
+    arith::{check_overflow, fixnum, NumberValue, Rounding},
     core::{
         cons::Cons,
         gc::Context,
         object::{Number, NumberType, Object},
     },
+    data::LispError,
 };
+use anyhow::{bail, Result};
 
 use rune_macros::defun;
 
@@ -18,24 +20,35 @@ fn coerce(arg: Number) -> f64 {
     }
 }
 
-#[defun]
-fn floor(arg: Number, divisor: Option<Number>) -> i64 {
-    let num = match divisor {
-        Some(div) => arg.val() / div.val(),
-        None => arg.val(),
+/// Convert `arg` divided by `divisor` to an integer, rounding as `mode`
+/// directs.
+fn rounding_driver(
+    arg: Number,
+    divisor: Option<Number>,
+    mode: Rounding,
+    cx: &Context,
+) -> Result<i64> {
+    let divisor = divisor.map_or(NumberValue::Int(1), |x| x.val());
+    let quot = match arg.val().checked_div(divisor, mode) {
+        Some((NumberValue::Int(quot), _)) => Some(quot),
+        Some((NumberValue::Float(quot), _)) if quot.is_finite() => {
+            (i64::MIN as f64..i64::MAX as f64).contains(&quot).then_some(quot as i64)
+        }
+        None if !matches!(divisor, NumberValue::Int(0)) => None,
+        _ => bail!(LispError::arith_error(cx)),
     };
-    match num {
-        NumberValue::Int(i) => i,
-        NumberValue::Float(f) => f.floor() as i64,
-    }
+    let NumberValue::Int(quot) = check_overflow(fixnum(quot), cx)? else { unreachable!() };
+    Ok(quot)
 }
 
 #[defun]
-fn ceiling(arg: Number) -> i64 {
-    match arg.untag() {
-        NumberType::Int(i) => i,
-        NumberType::Float(f) => f.ceil() as i64,
-    }
+fn floor(arg: Number, divisor: Option<Number>, cx: &Context) -> Result<i64> {
+    rounding_driver(arg, divisor, Rounding::Floor, cx)
+}
+
+#[defun]
+fn ceiling(arg: Number, divisor: Option<Number>, cx: &Context) -> Result<i64> {
+    rounding_driver(arg, divisor, Rounding::Ceiling, cx)
 }
 
 #[defun]
@@ -47,19 +60,13 @@ fn fceiling(arg: Number) -> f64 {
 }
 
 #[defun]
-fn round(arg: Number) -> i64 {
-    match arg.untag() {
-        NumberType::Int(i) => i,
-        NumberType::Float(f) => f.round() as i64,
-    }
+fn round(arg: Number, divisor: Option<Number>, cx: &Context) -> Result<i64> {
+    rounding_driver(arg, divisor, Rounding::Round, cx)
 }
 
 #[defun]
-fn truncate(arg: Number) -> i64 {
-    match arg.untag() {
-        NumberType::Int(i) => i,
-        NumberType::Float(f) => f.trunc() as i64,
-    }
+fn truncate(arg: Number, divisor: Option<Number>, cx: &Context) -> Result<i64> {
+    rounding_driver(arg, divisor, Rounding::Truncate, cx)
 }
 
 #[defun]
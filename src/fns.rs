@@ -2,7 +2,7 @@
 use crate::{
     core::{
         cons::Cons,
-        env::{sym, Env},
+        env::{sym, ArgSlice, Env},
         error::{Type, TypeError},
         gc::{Context, Rt, Rto},
         object::{
@@ -18,8 +18,10 @@ use anyhow::{anyhow, bail, ensure, Result};
 use base64::Engine;
 use fallible_iterator::FallibleIterator;
 use fallible_streaming_iterator::FallibleStreamingIterator;
+use rune_core::hashmap::HashMap;
 use rune_core::macros::{call, list, rebind, root};
 use rune_macros::{defun, elprop};
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 #[defun]
 fn identity(arg: Object) -> Object {
@@ -74,6 +76,92 @@ fn equal_including_properties<'ob>(o1: Object<'ob>, o2: Object<'ob>) -> bool {
     equal(o1, o2)
 }
 
+/// Reduce a 64 bit hash to a value that always fits in a fixnum.
+fn finish_sxhash(hasher: impl Hasher) -> i64 {
+    (hasher.finish() >> 9) as i64
+}
+
+/// Hash `obj` by identity. Heap objects and uninterned symbols are hashed by
+/// address, and the collector moves them, so their hash is only stable until
+/// the next garbage collection. Fixnums and interned symbols always hash the
+/// same.
+#[defun]
+fn sxhash_eq(obj: Object) -> i64 {
+    let mut hasher = DefaultHasher::new();
+    obj.hash(&mut hasher);
+    finish_sxhash(hasher)
+}
+
+/// Like [`sxhash_eq`], but floats are hashed by value.
+#[defun]
+fn sxhash_eql(obj: Object) -> i64 {
+    let mut hasher = DefaultHasher::new();
+    match obj.untag() {
+        ObjectType::Float(x) => x.to_bits().hash(&mut hasher),
+        _ => obj.hash(&mut hasher),
+    }
+    finish_sxhash(hasher)
+}
+
+#[defun]
+fn sxhash_equal(obj: Object) -> i64 {
+    let mut hasher = DefaultHasher::new();
+    hash_equal(obj, 0, &mut hasher);
+    finish_sxhash(hasher)
+}
+
+/// Hash `obj` so that objects that are `equal` hash the same. Like Emacs, only
+/// a bounded prefix of nested structure is hashed, which also keeps cyclic
+/// objects from looping forever.
+fn hash_equal<H: Hasher>(obj: Object, depth: usize, state: &mut H) {
+    const MAX_DEPTH: usize = 3;
+    const MAX_LEN: usize = 7;
+    let obj = obj.untag();
+    // Multibyte and unibyte strings with the same bytes hash alike, so their
+    // type is left out
+    if !matches!(obj, ObjectType::String(_) | ObjectType::ByteString(_)) {
+        std::mem::discriminant(&obj).hash(state);
+    }
+    if depth > MAX_DEPTH {
+        return;
+    }
+    match obj {
+        ObjectType::Int(x) => x.hash(state),
+        ObjectType::Float(x) => x.to_bits().hash(state),
+        ObjectType::Symbol(x) => x.hash(state),
+        ObjectType::String(x) => x.as_bytes().hash(state),
+        ObjectType::ByteString(x) => x.inner().hash(state),
+        ObjectType::Cons(cons) => {
+            let mut tail: Object = cons.into();
+            for _ in 0..MAX_LEN {
+                let ObjectType::Cons(cons) = tail.untag() else { break };
+                hash_equal(cons.car(), depth + 1, state);
+                tail = cons.cdr();
+            }
+            hash_equal(tail, depth + 1, state);
+        }
+        ObjectType::Vec(vec) => {
+            vec.len().hash(state);
+            for elem in vec.iter().take(MAX_LEN) {
+                hash_equal(elem.get(), depth + 1, state);
+            }
+        }
+        ObjectType::Record(record) => {
+            record.len().hash(state);
+            for elem in record.iter().take(MAX_LEN) {
+                hash_equal(elem.get(), depth + 1, state);
+            }
+        }
+        // These are compared by contents we don't hash, so the type is all we
+        // can use
+        ObjectType::HashTable(_)
+        | ObjectType::ByteFn(_)
+        | ObjectType::SubrFn(_)
+        | ObjectType::Buffer(_)
+        | ObjectType::CharTable(_) => {}
+    }
+}
+
 #[defun]
 fn plist_get<'ob>(plist: Object<'ob>, prop: Object<'ob>) -> Result<Object<'ob>> {
     let Ok(plist) = List::try_from(plist) else { return Ok(NIL) };
@@ -109,8 +197,13 @@ fn plist_member<'ob>(
 }
 
 #[defun]
-pub(crate) fn prin1_to_string(object: Object, _noescape: Option<Object>) -> String {
-    format!("{object}")
+pub(crate) fn prin1_to_string(object: Object, noescape: OptionalFlag) -> String {
+    match object.untag() {
+        // TODO: strings nested inside other objects are still escaped
+        ObjectType::String(string) if noescape.is_some() => string.inner().to_owned(),
+        ObjectType::Symbol(symbol) if noescape.is_some() => symbol.name().to_owned(),
+        _ => format!("{object}"),
+    }
 }
 
 #[defun]
@@ -157,6 +250,26 @@ pub(crate) fn mapcar<'ob>(
             // TODO: remove this intermediate vector
             Ok(slice_into_list(Rt::bind_slice(outputs, cx), None, cx))
         }
+        ObjectType::Vec(vec) => {
+            let len = vec.len();
+            root!(vec, cx);
+            root!(outputs, new(Vec), cx);
+            for i in 0..len {
+                let Some(val) = vec.bind(cx).get(i).map(|x| x.get()) else { break };
+                let output = call!(function, val; env, cx)?;
+                outputs.push(output);
+            }
+            Ok(slice_into_list(Rt::bind_slice(outputs, cx), None, cx))
+        }
+        ObjectType::String(string) => {
+            let chars: Vec<_> = string.chars().map(|x| i64::from(x as u32)).collect();
+            root!(outputs, new(Vec), cx);
+            for chr in chars {
+                let output = call!(function, chr; env, cx)?;
+                outputs.push(output);
+            }
+            Ok(slice_into_list(Rt::bind_slice(outputs, cx), None, cx))
+        }
         _ => Err(TypeError::new(Type::Sequence, sequence).into()),
     }
 }
@@ -164,18 +277,22 @@ pub(crate) fn mapcar<'ob>(
 #[defun]
 pub(crate) fn mapc<'ob>(
     function: &Rto<Function>,
-    sequence: &Rto<List>,
+    sequence: &Rto<Object>,
     env: &mut Rt<Env>,
     cx: &'ob mut Context,
 ) -> Result<Object<'ob>> {
     match sequence.untag(cx) {
-        ListType::Nil => Ok(NIL),
-        ListType::Cons(cons) => {
+        ObjectType::NIL => Ok(NIL),
+        ObjectType::Cons(cons) => {
             rooted_iter!(elements, cons, cx);
             while let Some(elem) = elements.next()? {
                 call!(function, elem; env, cx)?;
             }
-            Ok(sequence.bind(cx).into())
+            Ok(sequence.bind(cx))
+        }
+        _ => {
+            mapcar(function, sequence, env, cx)?;
+            Ok(sequence.bind(cx))
         }
     }
 }
@@ -260,10 +377,20 @@ pub(crate) fn nconc<'ob>(lists: &[List<'ob>]) -> Result<Object<'ob>> {
     })
 }
 
-fn join<'ob>(list: &mut Vec<Object<'ob>>, seq: List<'ob>) -> Result<()> {
-    if let ListType::Cons(cons) = seq.untag() {
-        for elt in cons {
-            list.push(elt?);
+/// Push the elements of the sequence `seq` onto `list`. Characters of strings
+/// become integers.
+fn join<'ob>(list: &mut Vec<Object<'ob>>, seq: Object<'ob>) -> Result<()> {
+    match seq.untag() {
+        ObjectType::String(string) => list.extend(string.chars().map(|ch| Object::from(ch as i64))),
+        ObjectType::ByteString(string) => {
+            list.extend(string.iter().map(|ch| Object::from(i64::from(*ch))))
+        }
+        ObjectType::Vec(vec) => list.extend(vec.iter().map(|x| x.get())),
+        _ => {
+            let Ok(seq) = List::try_from(seq) else { bail!(TypeError::new(Type::Sequence, seq)) };
+            for elt in seq {
+                list.push(elt?);
+            }
         }
     }
     Ok(())
@@ -276,30 +403,15 @@ fn take<'ob>(n: i64, list: List<'ob>, cx: &'ob Context) -> Result<Object<'ob>> {
 }
 
 #[defun]
-pub(crate) fn append<'ob>(
-    append: Object<'ob>,
-    sequences: &[Object<'ob>],
-    cx: &'ob Context,
-) -> Result<Object<'ob>> {
+pub(crate) fn append<'ob>(sequences: &[Object<'ob>], cx: &'ob Context) -> Result<Object<'ob>> {
+    let Some((&last, sequences)) = sequences.split_last() else { return Ok(NIL) };
     let mut list = Vec::new();
-    match append.untag() {
-        ObjectType::String(string) => {
-            for ch in string.chars() {
-                list.push((ch as i64).into());
-            }
-        }
-        ObjectType::ByteString(string) => {
-            for ch in string.iter() {
-                list.push((*ch as i64).into());
-            }
-        }
-        _ => join(&mut list, append.try_into()?)?,
-    }
     for seq in sequences {
-        join(&mut list, (*seq).try_into()?)?;
+        join(&mut list, *seq)?;
     }
+    // The last argument is not copied, it becomes the tail of the new list
     // TODO: Remove this temp vector
-    Ok(slice_into_list(&list, None, cx))
+    Ok(slice_into_list(&list, Some(last), cx))
 }
 
 #[defun]
@@ -394,12 +506,15 @@ fn copy_alist_elem<'ob>(elem: Object<'ob>, cx: &'ob Context) -> Object<'ob> {
     }
 }
 
-fn delete_from_list<'ob>(elt: Object<'ob>, list: List<'ob>, eq_fn: EqFunc) -> Result<Object<'ob>> {
+fn delete_from_list<'ob>(
+    list: List<'ob>,
+    mut should_delete: impl FnMut(Object<'ob>) -> bool,
+) -> Result<Object<'ob>> {
     let mut head = list.into();
     let mut prev: Option<&'ob Cons> = None;
     for tail in list.conses() {
         let tail = tail?;
-        if eq_fn(tail.car(), elt) {
+        if should_delete(tail.car()) {
             if let Some(prev_tail) = &mut prev {
                 prev_tail.set_cdr(tail.cdr())?;
             } else {
@@ -414,12 +529,153 @@ fn delete_from_list<'ob>(elt: Object<'ob>, list: List<'ob>, eq_fn: EqFunc) -> Re
 
 #[defun]
 pub(crate) fn delete<'ob>(elt: Object<'ob>, list: List<'ob>) -> Result<Object<'ob>> {
-    delete_from_list(elt, list, equal)
+    delete_from_list(list, |x| equal(x, elt))
 }
 
 #[defun]
 pub(crate) fn delq<'ob>(elt: Object<'ob>, list: List<'ob>) -> Result<Object<'ob>> {
-    delete_from_list(elt, list, eq)
+    delete_from_list(list, |x| eq(x, elt))
+}
+
+defsym!(KW_KEY);
+defsym!(KW_COUNT);
+
+/// How `cl-remove` and friends decide which elements to drop.
+enum RemoveTest<'a, 'rt> {
+    Eql(&'a Rto<Object<'rt>>),
+    Predicate(&'a Rto<Function<'rt>>),
+}
+
+/// Parse the `:key` and `:count` keyword arguments of the `cl-remove` family.
+/// A missing or nil `:count` removes every match.
+fn remove_keywords<'ob>(
+    keywords: &[Rto<Object>],
+    cx: &'ob Context,
+) -> Result<(Object<'ob>, Option<usize>)> {
+    ensure!(keywords.len().is_multiple_of(2), "Odd number of keyword arguments");
+    let mut key = NIL;
+    let mut count = None;
+    for pair in keywords.chunks_exact(2) {
+        let (keyword, value) = (pair[0].bind(cx), pair[1].bind(cx));
+        if keyword == sym::KW_KEY {
+            key = value;
+        } else if keyword == sym::KW_COUNT {
+            if !value.is_nil() {
+                let value: i64 = value.try_into()?;
+                count = Some(value.max(0) as usize);
+            }
+        } else {
+            // TODO: support :test, :start, :end and :from-end
+            bail!("Unsupported keyword argument: {keyword}");
+        }
+    }
+    Ok((key, count))
+}
+
+/// Build a fresh list of the elements of `list` that don't satisfy `test`.
+fn remove_matching<'ob>(
+    test: RemoveTest,
+    list: &Rto<Object>,
+    keywords: ArgSlice,
+    env: &mut Rt<Env>,
+    cx: &'ob mut Context,
+) -> Result<Object<'ob>> {
+    let (key, count) = remove_keywords(env.stack.arg_slice(keywords), cx)?;
+    root!(key, cx);
+    let key: Option<&Rto<Function>> = key.try_as_option()?;
+    let mut remaining = count.unwrap_or(usize::MAX);
+    root!(kept, new(Vec), cx);
+    rooted_iter!(iter, list, cx);
+    while let Some(elem) = iter.next()? {
+        if remaining > 0 {
+            let mut value = elem.bind(cx);
+            if let Some(key) = key {
+                value = rebind!(call!(key, elem; env, cx)?, cx);
+            }
+            let matched = match test {
+                RemoveTest::Eql(item) => eql(item.bind(cx), value),
+                RemoveTest::Predicate(pred) => call!(pred, value; env, cx)? != NIL,
+            };
+            if matched {
+                remaining -= 1;
+                continue;
+            }
+        }
+        kept.push(elem);
+    }
+    Ok(slice_into_list(Rt::bind_slice(kept, cx), None, cx))
+}
+
+/// Return a copy of `list` with the elements `eql` to `item` removed. Only the
+/// `:key` and `:count` keywords are supported, and any other keyword signals an
+/// error.
+#[defun]
+fn cl_remove<'ob>(
+    item: &Rto<Object>,
+    list: &Rto<Object>,
+    keywords: ArgSlice,
+    env: &mut Rt<Env>,
+    cx: &'ob mut Context,
+) -> Result<Object<'ob>> {
+    remove_matching(RemoveTest::Eql(item), list, keywords, env, cx)
+}
+
+/// Return a copy of `list` with the elements satisfying `predicate` removed.
+/// Only the `:key` and `:count` keywords are supported, and any other keyword
+/// signals an error.
+#[defun]
+fn cl_remove_if<'ob>(
+    predicate: &Rto<Function>,
+    list: &Rto<Object>,
+    keywords: ArgSlice,
+    env: &mut Rt<Env>,
+    cx: &'ob mut Context,
+) -> Result<Object<'ob>> {
+    remove_matching(RemoveTest::Predicate(predicate), list, keywords, env, cx)
+}
+
+#[defun]
+fn flatten_tree<'ob>(tree: Object<'ob>, cx: &'ob Context) -> Object<'ob> {
+    let mut leaves = Vec::new();
+    // Subtrees that still need to be walked, with the next one on top
+    let mut stack = vec![tree];
+    while let Some(mut node) = stack.pop() {
+        while let ObjectType::Cons(cons) = node.untag() {
+            stack.push(cons.cdr());
+            node = cons.car();
+        }
+        if !node.is_nil() {
+            leaves.push(node);
+        }
+    }
+    slice_into_list(&leaves, None, cx)
+}
+
+/// Destructively remove the elements of `list` that are `equal` to an earlier
+/// element, keeping the first occurrence of each.
+#[defun]
+fn delete_dups<'ob>(list: List<'ob>) -> Result<Object<'ob>> {
+    // Group the elements we keep by their `equal` hash so each element is only
+    // compared against the ones that could be `equal` to it
+    let mut seen: HashMap<u64, Vec<Object>> = HashMap::default();
+    delete_from_list(list, |x| {
+        let mut hasher = DefaultHasher::new();
+        hash_equal(x, 0, &mut hasher);
+        let bucket = seen.entry(hasher.finish()).or_default();
+        let dup = bucket.iter().any(|&y| equal(x, y));
+        if !dup {
+            bucket.push(x);
+        }
+        dup
+    })
+}
+
+#[defun]
+fn assq_delete_all<'ob>(key: Object<'ob>, alist: List<'ob>) -> Result<Object<'ob>> {
+    delete_from_list(alist, |x| match x.untag() {
+        ObjectType::Cons(cons) => eq(cons.car(), key),
+        _ => false,
+    })
 }
 
 fn member_of_list<'ob>(elt: Object<'ob>, list: List<'ob>, eq_fn: EqFunc) -> Result<Object<'ob>> {
@@ -448,14 +704,20 @@ pub(crate) fn member<'ob>(elt: Object<'ob>, list: List<'ob>) -> Result<Object<'o
 // TODO: Handle sorting vectors
 #[defun]
 fn sort<'ob>(
-    seq: &Rto<List>,
+    seq: &Rto<Object>,
     predicate: &Rto<Function>,
     env: &mut Rt<Env>,
     cx: &'ob mut Context,
 ) -> Result<Object<'ob>> {
-    let vec: Vec<_> = seq.bind(cx).elements().fallible().collect()?;
+    let vec: Vec<_> = match seq.bind(cx).untag() {
+        ObjectType::Vec(vec) => vec.iter().map(|x| x.get()).collect(),
+        _ => {
+            let list: List = seq.bind(cx).try_into()?;
+            list.elements().fallible().collect()?
+        }
+    };
     if vec.len() <= 1 {
-        return Ok(seq.bind(cx).into());
+        return Ok(seq.bind(cx));
     }
     root!(vec, cx);
     let mut err = None;
@@ -477,9 +739,19 @@ fn sort<'ob>(
             }
         }
     });
-    match err {
-        Some(e) => Err(e),
-        None => Ok(slice_into_list(Rt::bind_slice(vec, cx), None, cx)),
+    if let Some(e) = err {
+        return Err(e);
+    }
+    let seq = seq.bind(cx);
+    match seq.untag() {
+        // vectors are sorted in place
+        ObjectType::Vec(lisp_vec) => {
+            for (cell, val) in lisp_vec.try_mut()?.iter().zip(Rt::bind_slice(vec, cx)) {
+                cell.set(*val);
+            }
+            Ok(seq)
+        }
+        _ => Ok(slice_into_list(Rt::bind_slice(vec, cx), None, cx)),
     }
 }
 
@@ -525,11 +797,16 @@ pub(crate) fn require<'ob>(
 #[defun]
 pub(crate) fn concat(sequences: &[Object]) -> Result<String> {
     let mut concat = String::new();
+    let mut chars = Vec::new();
     for elt in sequences {
         match elt.untag() {
             ObjectType::String(string) => concat += string,
-            ObjectType::NIL => continue,
-            _ => bail!("Currently only concatenating strings are supported"),
+            _ => {
+                join(&mut chars, *elt)?;
+                for x in chars.drain(..) {
+                    concat.push(x.try_into()?);
+                }
+            }
         }
     }
     Ok(concat)
@@ -539,26 +816,7 @@ pub(crate) fn concat(sequences: &[Object]) -> Result<String> {
 pub(crate) fn vconcat<'ob>(sequences: &[Object], cx: &'ob Context) -> Result<Gc<&'ob LispVec>> {
     let mut concated: Vec<Object> = Vec::new();
     for elt in sequences {
-        match elt.untag() {
-            // TODO: need to correctly handle unibyte strings (no unicode codepoints)
-            ObjectType::String(string) => {
-                for chr in string.chars() {
-                    concated.push((chr as i64).into());
-                }
-            }
-            ObjectType::Cons(cons) => {
-                for x in cons {
-                    concated.push(x?);
-                }
-            }
-            ObjectType::Vec(vec) => {
-                for x in vec.iter() {
-                    concated.push(x.get());
-                }
-            }
-            ObjectType::NIL => {}
-            obj => bail!(TypeError::new(Type::Sequence, obj)),
-        }
+        join(&mut concated, *elt)?;
     }
     Ok(concated.into_obj(cx))
 }
@@ -621,6 +879,7 @@ pub(crate) fn elt<'ob>(sequence: Object<'ob>, n: usize, cx: &'ob Context) -> Res
         ObjectType::Vec(x) => aref(x.into(), n, cx),
         ObjectType::Record(x) => aref(x.into(), n, cx),
         ObjectType::String(x) => aref(x.into(), n, cx),
+        ObjectType::ByteString(x) => aref(x.into(), n, cx),
         ObjectType::ByteFn(x) => aref(x.into(), n, cx),
         other => Err(TypeError::new(Type::Sequence, other).into()),
     }
@@ -825,7 +1084,7 @@ pub(crate) fn string_version_lessp<'ob>(
 
 #[defun]
 pub(crate) fn clear_string(string: &LispString) -> Result<Object> {
-    string.clear();
+    string.clear()?;
     Ok(NIL)
 }
 
@@ -938,16 +1197,12 @@ fn copy_sequence<'ob>(arg: Object<'ob>, cx: &'ob Context) -> Result<Object<'ob>>
         ObjectType::Vec(x) => Ok(cx.add(x.to_vec())),
         ObjectType::Cons(x) => {
             // TODO: remove this temp vector
-            let mut elements = Vec::new();
-            let mut tail = None;
-            for cons in x.conses() {
-                let cons = cons?;
-                elements.push(cons.car());
-                if !matches!(cons.cdr().untag(), ObjectType::Cons(_)) {
-                    tail = Some(cons.cdr());
-                }
+            let mut iter = x.iter(cx);
+            let elements: Vec<_> = iter.by_ref().collect::<Result<_, _>>()?;
+            match iter.tail() {
+                tail if tail.is_nil() => Ok(slice_into_list(&elements, None, cx)),
+                tail => Err(TypeError::new(Type::List, tail).into()),
             }
-            Ok(slice_into_list(&elements, tail, cx))
         }
         ObjectType::String(x) => Ok(cx.add(x.to_owned())),
         ObjectType::NIL => Ok(NIL),
@@ -956,20 +1211,19 @@ fn copy_sequence<'ob>(arg: Object<'ob>, cx: &'ob Context) -> Result<Object<'ob>>
 }
 
 #[defun]
-fn substring(string: &str, from: Option<usize>, to: Option<usize>) -> Result<String> {
-    if from.unwrap_or_default() > string.len() || to.unwrap_or_default() > string.len() {
-        bail!("substring args out of range for {string} : {from:?} {to:?}");
+fn substring(string: &str, from: Option<i64>, to: Option<i64>, cx: &Context) -> Result<String> {
+    let len = string.chars().count() as i64;
+    let normalize = |idx: i64| if idx < 0 { idx + len } else { idx };
+    let start = normalize(from.unwrap_or(0));
+    let end = normalize(to.unwrap_or(len));
+    if start < 0 || end > len || start > end {
+        let args = [cx.add(string), cx.add(from), cx.add(to)];
+        bail!(crate::data::LispError::args_out_of_range(&args, cx));
     }
-    let new_string = match (from, to) {
-        (None, None) => string,
-        (None, Some(t)) => &string[..t],
-        (Some(f), None) => &string[f..],
-        (Some(f), Some(t)) => {
-            let range = if f > t { t..f } else { f..t };
-            &string[range]
-        }
-    };
-    Ok(new_string.to_owned())
+    // Indices are in characters, so convert them to byte offsets
+    let byte_offset =
+        |idx: i64| string.char_indices().nth(idx as usize).map_or(string.len(), |x| x.0);
+    Ok(string[byte_offset(start)..byte_offset(end)].to_owned())
 }
 
 defsym!(MD5);
@@ -1052,6 +1306,46 @@ mod test {
         // assert_lisp("(base64-encode-string \"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum\" t)", "\"TG9yZW0gaXBzdW0gZG9sb3Igc2l0IGFtZXQsIGNvbnNlY3RldHVyIGFkaXBpc2NpbmcgZWxpdCwg\nc2VkIGRvIGVpdXNtb2QgdGVtcG9yIGluY2lkaWR1bnQgdXQgbGFib3JlIGV0IGRvbG9yZSBtYWdu\nYSBhbGlxdWEuIFV0IGVuaW0gYWQgbWluaW0gdmVuaWFtLCBxdWlzIG5vc3RydWQgZXhlcmNpdGF0\naW9uIHVsbGFtY28gbGFib3JpcyBuaXNpIHV0IGFsaXF1aXAgZXggZWEgY29tbW9kbyBjb25zZXF1\nYXQuIER1aXMgYXV0ZSBpcnVyZSBkb2xvciBpbiByZXByZWhlbmRlcml0IGluIHZvbHVwdGF0ZSB2\nZWxpdCBlc3NlIGNpbGx1bSBkb2xvcmUgZXUgZnVnaWF0IG51bGxhIHBhcmlhdHVyLiBFeGNlcHRl\ndXIgc2ludCBvY2NhZWNhdCBjdXBpZGF0YXQgbm9uIHByb2lkZW50LCBzdW50IGluIGN1bHBhIHF1\naSBvZmZpY2lhIGRlc2VydW50IG1vbGxpdCBhbmltIGlkIGVzdCBsYWJvcnVt\"");
     }
 
+    #[test]
+    fn test_copy_sequence() {
+        assert_lisp("(copy-sequence '(1 2 3))", "(1 2 3)");
+        assert_lisp("(let ((x (list 1 2))) (eq x (copy-sequence x)))", "nil");
+        assert_lisp("(condition-case nil (copy-sequence '(1 2 . 3)) (error 'err))", "err");
+    }
+
+    #[test]
+    fn test_mapcar() {
+        assert_lisp("(mapcar #'1+ '(1 2 3))", "(2 3 4)");
+        assert_lisp("(mapcar #'1+ nil)", "nil");
+        assert_lisp("(mapcar #'1+ [1 2 3])", "(2 3 4)");
+        assert_lisp("(mapcar #'identity \"ab\")", "(97 98)");
+        assert_lisp("(let ((x '(1 2)) (sum 0)) (list (eq (mapc #'(lambda (y) (setq sum (+ sum y))) x) x) x sum))", "(t (1 2) 3)");
+        assert_lisp("(let ((n 0)) (mapc #'(lambda (_) (setq n (1+ n))) [a b c]) n)", "3");
+    }
+
+    #[test]
+    fn test_mapconcat() {
+        assert_lisp("(mapconcat #'number-to-string '(1 2 3) \",\")", "\"1,2,3\"");
+        assert_lisp("(mapconcat #'number-to-string [1.5 -2] \" \")", "\"1.5 -2\"");
+        assert_lisp("(mapconcat #'identity '(\"a\" \"b\"))", "\"ab\"");
+        assert_lisp("(mapconcat #'identity nil \",\")", "\"\"");
+        assert_lisp("(condition-case nil (mapconcat #'1+ '(1 2) \",\") (error 'err))", "err");
+    }
+
+    #[test]
+    fn test_prin1_to_string() {
+        assert_lisp(r#"(prin1-to-string (read "(1 \"two\" three)"))"#, r#""(1 \"two\" three)""#);
+        assert_lisp("(prin1-to-string '(1 . 2))", r#""(1 . 2)""#);
+        assert_lisp("(prin1-to-string 1.0)", r#""1.0""#);
+        assert_lisp(r#"(prin1-to-string "a\"b")"#, r#""\"a\\\"b\"""#);
+        assert_lisp(r#"(prin1-to-string "a\"b" t)"#, r#""a\"b""#);
+        assert_lisp(r#"(prin1-to-string 'a\ b)"#, r#""a\\ b""#);
+        assert_lisp(r#"(prin1-to-string 'a\ b t)"#, r#""a b""#);
+        assert_lisp(r#"(prin1-to-string (intern "1"))"#, r#""\\1""#);
+        assert_lisp("(prin1-to-string 'inf)", r#""inf""#);
+        assert_lisp("(let ((x (list 1))) (setcdr x x) (prin1-to-string x))", r#""(1 . #0)""#);
+    }
+
     #[test]
     fn test_take() {
         assert_lisp("(take 2 '(1 2 3 4))", "(1 2)");
@@ -1061,6 +1355,128 @@ mod test {
     fn test_delq() {
         assert_lisp("(delq 1 '(1 2 3 1 4 1))", "(2 3 4)");
         assert_lisp("(delq t '(t t t))", "nil");
+        assert_lisp("(delq 2 '(1 2 3 2))", "(1 3)");
+        assert_lisp("(delq 1 '(1 2 3))", "(2 3)");
+        assert_lisp("(let ((x (list 1 2 3))) (delq 2 x) x)", "(1 3)");
+    }
+
+    #[test]
+    fn test_cl_remove() {
+        assert_lisp("(cl-remove 2 '(1 2 3 2))", "(1 3)");
+        assert_lisp("(cl-remove 2 nil)", "nil");
+        assert_lisp("(cl-remove 1.0 '(1 1.0 2))", "(1 2)");
+        assert_lisp("(let ((x (list 1 2 3))) (cl-remove 2 x) x)", "(1 2 3)");
+        assert_lisp("(let ((x (list 1 2 3))) (eq (cl-remove 4 x) x))", "nil");
+        assert_lisp("(cl-remove 2 '(1 2 3 2 2) :count 2)", "(1 3 2)");
+        assert_lisp("(cl-remove 'a '((a . 1) (b . 2)) :key 'car)", "((b . 2))");
+        assert_lisp("(cl-remove 2 '(1 2) :count 0)", "(1 2)");
+        assert_lisp("(cl-remove 2 '(2 1 2) :count nil)", "(1)");
+        // keywords other than :key and :count are not supported
+        assert_lisp("(condition-case nil (cl-remove 1 '(1) :test 'eq) (error 'err))", "err");
+        assert_lisp("(condition-case nil (cl-remove 1 '(1) :start 1) (error 'err))", "err");
+        assert_lisp("(condition-case nil (cl-remove 1 '(1) :from-end t) (error 'err))", "err");
+        assert_lisp("(condition-case nil (cl-remove 1 '(1) :count) (error 'err))", "err");
+    }
+
+    #[test]
+    fn test_cl_remove_if() {
+        assert_lisp("(cl-remove-if 'cl-evenp '(1 2 3 4 5))", "(1 3 5)");
+        assert_lisp("(cl-remove-if 'cl-evenp '(1 2 3 4) :count 1)", "(1 3 4)");
+        assert_lisp("(cl-remove-if 'cl-evenp '((1) (2) (3)) :key 'car)", "((1) (3))");
+        assert_lisp("(cl-remove-if #'(lambda (x) (> x 1)) '(3 1 2))", "(1)");
+        assert_lisp("(let ((x (list 1 2))) (cl-remove-if 'cl-evenp x) x)", "(1 2)");
+        assert_lisp(
+            "(condition-case nil (cl-remove-if 'cl-evenp '(1) :end 1) (error 'err))",
+            "err",
+        );
+        assert_lisp(
+            "(condition-case nil (cl-remove-if 'cl-evenp '((1)) :key 'car :if-not t) (error 'err))",
+            "err",
+        );
+    }
+
+    #[test]
+    fn test_flatten_tree() {
+        assert_lisp("(flatten-tree '(1 (2 (3 4)) 5))", "(1 2 3 4 5)");
+        assert_lisp("(flatten-tree '(1 (2 . 3) nil (4 5 (6)) 7))", "(1 2 3 4 5 6 7)");
+        assert_lisp("(flatten-tree nil)", "nil");
+        assert_lisp("(flatten-tree 'a)", "(a)");
+        assert_lisp("(let ((x (list 1 2))) (eq (flatten-tree x) x))", "nil");
+    }
+
+    #[test]
+    fn test_flatten_deep_tree() {
+        use super::*;
+        use crate::core::gc::RootSet;
+        let roots = &RootSet::default();
+        let cx = &Context::new(roots);
+        // deep enough to overflow the stack if walked recursively
+        let mut tree = cx.add(1);
+        for _ in 0..1_000_000 {
+            tree = Cons::new1(tree, cx).into();
+        }
+        let tree = list![tree, 2, tree; cx];
+        assert_eq!(flatten_tree(tree, cx), list![1, 2, 1; cx]);
+    }
+
+    #[test]
+    fn test_delete_dups() {
+        assert_lisp("(delete-dups '(1 2 1 3 2))", "(1 2 3)");
+        assert_lisp("(delete-dups nil)", "nil");
+        assert_lisp("(delete-dups '(a a a))", "(a)");
+        assert_lisp("(delete-dups '(\"a\" (1) \"a\" (1) 1.0))", "(\"a\" (1) 1.0)");
+        assert_lisp("(let ((x (list 1 2 2 3 1))) (delete-dups x) x)", "(1 2 3)");
+        assert_lisp(
+            "(let ((x nil) (i 0)) (while (< i 200) (setq x (cons (list (% i 50)) x)) (setq i (1+ i))) (length (delete-dups x)))",
+            "50",
+        );
+    }
+
+    #[test]
+    fn test_sxhash() {
+        assert_lisp("(= (sxhash-equal '(1 2)) (sxhash-equal '(1 2)))", "t");
+        assert_lisp(
+            "(= (sxhash-equal (list \"a\" [1.5 b])) (sxhash-equal (list \"a\" [1.5 b])))",
+            "t",
+        );
+        assert_lisp("(= (sxhash-equal '(1 2)) (sxhash-equal '(2 1)))", "nil");
+        assert_lisp("(= (sxhash-eq 'x) (sxhash-eq 'x))", "t");
+        assert_lisp("(= (sxhash-eq 'x) (sxhash-eq 'y))", "nil");
+        assert_lisp("(= (sxhash-eql 1.5) (sxhash-eql 1.5))", "t");
+        assert_lisp("(integerp (sxhash-equal (let ((x (list 1))) (setcdr x x) x)))", "t");
+        assert_lisp("(= (sxhash-equal \"ab\") (sxhash-equal (unibyte-string 97 98)))", "t");
+        // only fixnums and interned symbols keep their identity hash across a
+        // collection, since heap objects are moved
+        assert_lisp(
+            "(let ((h (sxhash-eq 'x)) (i (sxhash-eq 7))) (garbage-collect) (and (= h (sxhash-eq 'x)) (= i (sxhash-eq 7))))",
+            "t",
+        );
+        assert_lisp(
+            "(let* ((x (list 1)) (h (sxhash-eq x))) (garbage-collect) (= h (sxhash-eq x)))",
+            "nil",
+        );
+    }
+
+    #[test]
+    fn test_assq_delete_all() {
+        assert_lisp("(assq-delete-all 'a '((a . 1) (b . 2) (a . 3)))", "((b . 2))");
+        assert_lisp("(assq-delete-all 'b '((a . 1) b (b . 2)))", "((a . 1) b)");
+        assert_lisp("(assq-delete-all 'a '((a . 1)))", "nil");
+    }
+
+    #[test]
+    fn test_circular_list() {
+        let circular = |call: &str| {
+            format!(
+                "(let ((x (list 1 2 3))) (setcdr (cdr (cdr x)) x) (condition-case err {call} (circular-list err)))"
+            )
+        };
+        assert_lisp(&circular("(length x)"), "(circular-list)");
+        assert_lisp(&circular("(reverse x)"), "(circular-list)");
+        assert_lisp(&circular("(append x nil)"), "(circular-list)");
+        assert_lisp(&circular("(memq 4 x)"), "(circular-list)");
+        assert_lisp(&circular("(safe-length x)"), "3");
+        assert_lisp("(length '(1 2 3))", "3");
     }
 
     #[test]
@@ -1082,6 +1498,13 @@ mod test {
         assert_lisp("(nreverse '(1 2))", "(2 1)");
         assert_lisp("(nreverse '(1 2 3))", "(3 2 1)");
         assert_lisp("(nreverse '(1 2 3 4))", "(4 3 2 1)");
+        assert_lisp("(let ((x (list 1 2 3))) (nreverse x) x)", "(1)");
+        assert_lisp("(condition-case nil (nreverse '(1 2 . 3)) (error 'err))", "err");
+        assert_lisp("(reverse nil)", "nil");
+        assert_lisp("(reverse '(1))", "(1)");
+        assert_lisp("(reverse '(1 2 3))", "(3 2 1)");
+        assert_lisp("(let* ((x (list 1 2 3)) (y (reverse x))) (list x y))", "((1 2 3) (3 2 1))");
+        assert_lisp("(condition-case nil (reverse '(1 2 . 3)) (error 'err))", "err");
     }
 
     #[test]
@@ -1096,19 +1519,123 @@ mod test {
 
     #[test]
     fn test_append() {
-        assert_lisp("(append \"hello\")", "(104 101 108 108 111)");
+        assert_lisp("(append)", "nil");
+        assert_lisp("(append nil)", "nil");
+        assert_lisp("(append '(1 2) '(3) nil '(4 5))", "(1 2 3 4 5)");
+        assert_lisp("(append \"hello\" nil)", "(104 101 108 108 111)");
+        assert_lisp("(append [1 2] '(3))", "(1 2 3)");
+        // the last argument is used as the tail
+        assert_lisp("(append '(1) 2)", "(1 . 2)");
+        assert_lisp("(append \"hello\")", "\"hello\"");
+        assert_lisp("(let ((x (list 3))) (eq (cdr (append '(1) x)) x))", "t");
+        assert_lisp("(condition-case nil (append 1 '(2)) (error 'err))", "err");
+        assert_lisp("(append [1 2] \"ab\" '(3) [4])", "(1 2 97 98 3 . [4])");
+        assert_lisp("(append \"ab\" nil)", "(97 98)");
+        assert_lisp("(append (unibyte-string 1 2) nil)", "(1 2)");
+    }
+
+    #[test]
+    fn test_concat_vconcat() {
+        assert_lisp("(concat \"ab\" '(99) [100] nil)", "\"abcd\"");
+        assert_lisp("(concat)", "\"\"");
+        assert_lisp("(vconcat '(1) [2] \"c\" nil)", "[1 2 99]");
+        assert_lisp("(condition-case nil (concat '(a)) (error 'err))", "err");
+        assert_lisp("(condition-case nil (vconcat 1) (error 'err))", "err");
     }
 
     #[test]
     fn test_assq() {
         assert_lisp("(assq 5 '((1 . 2) (3 . 4) (5 . 6)))", "(5 . 6)");
         assert_lisp("(assq 6 '((1 . 2) (3 . 4) (5 . 6)))", "nil");
+        assert_lisp("(assq 'x '((x . 1)))", "(x . 1)");
+        assert_lisp("(assq 'x nil)", "nil");
+        assert_lisp("(assq \"x\" '((\"x\" . 1)))", "nil");
+    }
+
+    #[test]
+    fn test_assoc() {
+        assert_lisp("(assoc \"x\" '((\"y\" . 2) (\"x\" . 1)))", "(\"x\" . 1)");
+        assert_lisp("(assoc '(1) '((a . 2) ((1) . 3)))", "((1) . 3)");
+        assert_lisp("(assoc 'z '((x . 1)))", "nil");
+        assert_lisp("(assoc 3 '((1 . 2) (3 . 4)) #'=)", "(3 . 4)");
+    }
+
+    #[test]
+    fn test_member() {
+        assert_lisp("(memq 'b '(a b c))", "(b c)");
+        assert_lisp("(memq 'd '(a b c))", "nil");
+        assert_lisp("(memq 'a nil)", "nil");
+        assert_lisp("(memq \"b\" '(\"a\" \"b\"))", "nil");
+        assert_lisp("(member \"b\" '(\"a\" \"b\" \"c\"))", "(\"b\" \"c\")");
+        assert_lisp("(member '(1) '(0 (1) 2))", "((1) 2)");
+        assert_lisp("(member 3 '(1 2))", "nil");
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_lisp("(concat)", "\"\"");
+        assert_lisp("(concat \"ab\" \"cd\")", "\"abcd\"");
+        assert_lisp("(concat \"ab\" nil \"cd\")", "\"abcd\"");
+        assert_lisp("(concat '(104 105) [33])", "\"hi!\"");
+        assert_lisp("(concat \"λ\" '(955))", "\"λλ\"");
+        assert_lisp("(condition-case nil (concat '(a)) (error 'err))", "err");
+        assert_lisp("(condition-case nil (concat [-1]) (error 'err))", "err");
+        assert_lisp("(condition-case nil (concat 1) (error 'err))", "err");
+    }
+
+    #[test]
+    fn test_vconcat() {
+        assert_lisp("(vconcat)", "[]");
+        assert_lisp("(vconcat '(1 2) [3 4])", "[1 2 3 4]");
+        assert_lisp("(vconcat \"ab\")", "[97 98]");
+        assert_lisp("(vconcat nil \"λ\" '(a) [\"b\"])", "[955 a \"b\"]");
+        assert_lisp("(condition-case nil (vconcat 1) (error 'err))", "err");
+    }
+
+    #[test]
+    fn test_substring() {
+        assert_lisp("(substring \"hello\" 1 3)", "\"el\"");
+        assert_lisp("(substring \"hello\")", "\"hello\"");
+        assert_lisp("(substring \"hello\" nil 2)", "\"he\"");
+        assert_lisp("(substring \"hello\" -2)", "\"lo\"");
+        assert_lisp("(substring \"hello\" 1 -1)", "\"ell\"");
+        assert_lisp("(substring \"hello\" 5)", "\"\"");
+        assert_lisp("(substring \"λx→y\" 1 3)", "\"x→\"");
+        assert_lisp(
+            "(condition-case err (substring \"hello\" 2 6) (error err))",
+            "(args-out-of-range \"hello\" 2 6)",
+        );
+        assert_lisp(
+            "(condition-case err (substring \"hello\" 3 1) (error (car err)))",
+            "args-out-of-range",
+        );
+        assert_lisp(
+            "(condition-case err (substring \"hello\" -6) (error (car err)))",
+            "args-out-of-range",
+        );
+    }
+
+    #[test]
+    fn test_elt() {
+        assert_lisp("(elt '(a b c) 1)", "b");
+        assert_lisp("(elt [a b c] 2)", "c");
+        assert_lisp("(elt \"abc\" 0)", "97");
+        assert_lisp("(elt '(a b c) 5)", "nil");
+        assert_lisp("(elt nil 0)", "nil");
+        assert_lisp("(condition-case err (elt [a b c] 3) (args-out-of-range 'range))", "range");
+        assert_lisp("(condition-case nil (elt \"abc\" 3) (error 'err))", "err");
+        assert_lisp("(condition-case nil (elt 1 0) (error 'err))", "err");
     }
 
     #[test]
     fn test_string_equal() {
         assert_lisp("(string-equal \"hello\" \"hello\")", "t");
         assert_lisp("(string-equal \"hello\" \"world\")", "nil");
+        assert_lisp(r#"(string-equal 'foo "foo")"#, "t");
+        assert_lisp(r#"(string-equal "foo" 'foo)"#, "t");
+        assert_lisp("(string-equal 'foo 'bar)", "nil");
+        assert_lisp(r#"(string-equal "" nil)"#, "nil");
+        assert_lisp(r#"(condition-case nil (string-equal 1 "1") (error 'err))"#, "err");
     }
 
     #[test]
@@ -1289,6 +1816,9 @@ mod test {
             "((1 . 1) (1 . 2) (1 . 3))",
         );
         assert_lisp("(condition-case nil (sort '(3 2 1) 'length) (error 7))", "7");
+        assert_lisp("(sort (vector) '<)", "[]");
+        assert_lisp("(sort (vector 3 1 2) '<)", "[1 2 3]");
+        assert_lisp("(let ((v (vector 3 1 2))) (sort v '>) v)", "[3 2 1]");
     }
 
     #[test]
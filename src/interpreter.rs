@@ -5,10 +5,13 @@ use crate::{
         env::{sym, CallFrame, Env},
         error::{Type, TypeError},
         gc::{Context, Rt, Rto, Slot},
-        object::{Function, Gc, List, ListType, Object, ObjectType, Symbol, TagType, NIL, TRUE},
+        object::{
+            Function, FunctionType, Gc, List, ListType, Number, Object, ObjectType, Symbol,
+            TagType, NIL, TRUE,
+        },
     },
     data::LispError,
-    eval::{add_trace, ErrorType, EvalError, EvalResult},
+    eval::{add_trace, handles_error, ErrorType, EvalError, EvalResult},
     rooted_iter,
 };
 use anyhow::Context as _;
@@ -21,6 +24,11 @@ use rune_macros::defun;
 struct Interpreter<'brw, 'rt> {
     vars: &'brw mut Rt<Vec<Slot<&'rt Cons>>>,
     env: &'brw mut Rt<Env<'rt>>,
+    /// Where calls to closures in tail position are stored instead of being
+    /// made, as `(name function args...)`. Only set for closure bodies.
+    tail_call: Option<&'brw mut Rt<Vec<Slot<Object<'rt>>>>>,
+    /// True if the next form evaluated is in tail position.
+    tail: bool,
 }
 
 #[defun]
@@ -39,17 +47,18 @@ pub(crate) fn eval<'ob>(
             }
         }
     }
-    let mut interpreter = Interpreter { vars, env };
+    let mut interpreter = Interpreter { vars, env, tail_call: None, tail: false };
     interpreter.eval_form(form, cx).map_err(Into::into)
 }
 
 impl Interpreter<'_, '_> {
     fn eval_form<'ob>(&mut self, rt: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+        let tail = std::mem::take(&mut self.tail);
         match rt.untag(cx) {
             ObjectType::Symbol(sym) => self.var_ref(sym, cx),
             ObjectType::Cons(_) => {
                 let x = rt.try_as().unwrap();
-                self.eval_sexp(x, cx)
+                self.eval_sexp(x, tail, cx)
             }
             _ => Ok(rt.bind(cx)),
         }
@@ -58,6 +67,7 @@ impl Interpreter<'_, '_> {
     pub(crate) fn eval_sexp<'ob>(
         &mut self,
         cons: &Rto<Gc<&Cons>>,
+        tail: bool,
         cx: &'ob mut Context,
     ) -> EvalResult<'ob> {
         let cons = cons.bind(cx);
@@ -68,27 +78,38 @@ impl Interpreter<'_, '_> {
                 sym::QUOTE => self.quote(forms.bind(cx), cx),
                 sym::LET => self.eval_let(forms, true, cx),
                 sym::LET_STAR => self.eval_let(forms, false, cx),
-                sym::IF => self.eval_if(forms, cx),
-                sym::AND => self.eval_and(forms, cx),
-                sym::OR => self.eval_or(forms, cx),
-                sym::COND => self.eval_cond(forms, cx),
+                sym::IF => self.eval_if(forms, tail, cx),
+                sym::WHEN => self.eval_when(forms, true, tail, cx),
+                sym::UNLESS => self.eval_when(forms, false, tail, cx),
+                sym::AND => self.eval_and(forms, tail, cx),
+                sym::OR => self.eval_or(forms, tail, cx),
+                sym::COND => self.eval_cond(forms, tail, cx),
                 sym::WHILE => self.eval_while(forms, cx),
-                sym::PROGN | sym::INLINE => self.eval_progn(forms, cx),
+                sym::DOLIST => self.dolist(forms, cx),
+                sym::DOTIMES => self.dotimes(forms, cx),
+                sym::CL_INCF | sym::CL_DECF if Self::is_variable_place(forms.bind(cx)) => {
+                    self.incf(forms, sym == sym::CL_INCF, cx)
+                }
+                sym::PROGN | sym::INLINE => {
+                    self.tail = tail;
+                    self.eval_progn(forms, cx)
+                }
                 sym::PROG1 => self.eval_progx(forms, 1, cx),
                 sym::PROG2 => self.eval_progx(forms, 2, cx),
                 sym::SETQ => self.setq(forms, cx),
-                sym::DEFVAR | sym::DEFCONST => self.defvar(forms, cx),
+                sym::DEFVAR => self.defvar(forms, false, cx),
+                sym::DEFCONST => self.defvar(forms, true, cx),
                 sym::FUNCTION => self.eval_function(forms, cx),
                 sym::INTERACTIVE => Ok(NIL), // TODO: implement
                 sym::CATCH => self.catch(forms, cx),
-                sym::THROW => self.throw(forms.bind(cx), cx),
+                sym::THROW => self.throw(forms, cx),
                 sym::CONDITION_CASE => self.condition_case(forms, cx),
                 sym::SAVE_CURRENT_BUFFER => self.save_current_buffer(forms, cx),
                 sym::SAVE_EXCURSION => self.save_excursion(forms, cx),
                 sym::UNWIND_PROTECT => self.unwind_protect(forms, cx),
                 _ => {
                     root!(sym, cx);
-                    self.eval_call(sym, forms, cx)
+                    self.eval_call(sym, forms, tail, cx)
                 }
             },
             other => Err(error!("Invalid Function: {other}")),
@@ -100,17 +121,18 @@ impl Interpreter<'_, '_> {
         let Some(tag) = forms.next()? else {
             bail_err!(LispError::arg_cnt(sym::CATCH, 1, 0, cx))
         };
+        let tag = rebind!(self.eval_form(tag, cx)?);
         // push this tag on the catch stack
-        self.env.catch_stack.push(tag);
+        self.env.push_catch(tag);
         let result = match self.implicit_progn(forms, cx) {
             Ok(x) => Ok(rebind!(x, cx)),
-            Err(e) => {
+            Err(e) => 'caught: {
                 if let ErrorType::Throw(id) = e.error {
                     if let Some((throw_tag, data)) = self.env.get_exception(id) {
-                        let catch_tag = self.env.catch_stack.last().unwrap();
+                        let catch_tag = self.env.current_catch().unwrap();
                         // TODO: Remove binds
                         if catch_tag == throw_tag {
-                            return Ok(data.bind(cx));
+                            break 'caught Ok(data.bind(cx));
                         }
                     }
                 }
@@ -118,47 +140,71 @@ impl Interpreter<'_, '_> {
             }
         };
         // pop this tag from the catch stack
-        self.env.catch_stack.pop();
+        self.env.pop_catch();
         result
     }
 
-    fn throw<'ob>(&mut self, obj: Object, cx: &'ob Context) -> EvalResult<'ob> {
-        let [tag, value] = match obj.into_array()? {
+    fn throw<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+        let [tag, value] = match obj.bind(cx).into_array()? {
             Ok(x) => x,
             Err(e) => bail_err!(LispError::arg_cnt(sym::THROW, 2, e, cx)),
         };
+        root!(value, cx);
+        root!(tag, cx);
+        let tag = rebind!(self.eval_form(tag, cx)?);
+        root!(tag, cx);
+        let value = rebind!(self.eval_form(value, cx)?);
+        let tag = tag.bind(cx);
 
         // Need to check now that there is a catch, because we may have a
         // condition-case along the unwind path
-        if self.env.catch_stack.iter().any(|x| x.bind(cx) == tag) {
+        if self.env.is_catching(tag, cx) {
             Err(EvalError::throw(tag, value, self.env))
         } else {
-            Err(error!("No catch for {tag}"))
+            Err(LispError::no_catch(tag, value, cx).into())
         }
     }
 
-    fn defvar<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+    fn defvar<'ob>(
+        &mut self,
+        obj: &Rto<Object>,
+        is_const: bool,
+        cx: &'ob mut Context,
+    ) -> EvalResult<'ob> {
         rooted_iter!(forms, obj, cx);
         // (defvar x ...)                 // (defvar)
         let Some(sym) = forms.next()? else {
-            bail_err!(LispError::arg_cnt(sym::DEFVAR, 1, 0, cx))
+            let name = if is_const { sym::DEFCONST } else { sym::DEFVAR };
+            bail_err!(LispError::arg_cnt(name, 1, 0, cx))
         };
         let name: Symbol = sym.bind(cx).try_into()?;
         root!(name, cx);
-        let value = match forms.next()? {
-            // (defvar x y)
-            Some(value) => rebind!(self.eval_form(value, cx)?),
+        match forms.next()? {
+            // defvar does not evaluate the value if the variable is already
+            // bound, but defconst always sets it
+            Some(value) if is_const || self.env.toplevel_value(name.bind(cx), cx).is_none() => {
+                let value = rebind!(self.eval_form(value, cx)?);
+                if is_const {
+                    self.env.set_var(name.bind(cx), value)?;
+                }
+                self.env.defvar(name.bind(cx), value)?;
+            }
+            Some(_) => name.bind(cx).make_special(),
             // (defvar x)
-            None => NIL,
-        };
-        self.env.defvar(name.bind(cx), value)?;
-        Ok(value)
+            None => self.env.defvar(name.bind(cx), NIL)?,
+        }
+        if let Some(doc) = forms.next()? {
+            let doc = doc.bind(cx);
+            self.env.set_prop(name.bind(cx), sym::VARIABLE_DOCUMENTATION, doc);
+        }
+        Ok(name.bind(cx).into())
     }
 
     fn eval_call<'ob>(
         &mut self,
         sym: &Rto<Symbol>,
         args: &Rto<Object>,
+        tail: bool,
         cx: &'ob mut Context,
     ) -> EvalResult<'ob> {
         let Some(func) = sym.bind(cx).follow_indirect(cx) else {
@@ -183,6 +229,7 @@ impl Interpreter<'_, '_> {
                 let value = mcro.call(&mut frame, Some(&name), cx)?;
                 drop(frame);
                 root!(value, cx);
+                self.tail = tail;
                 return self.eval_form(value, cx);
             }
             _ => {}
@@ -194,10 +241,18 @@ impl Interpreter<'_, '_> {
             let result = self.eval_form(x, cx)?;
             args.push(result);
         }
-        let frame = &mut CallFrame::new(self.env);
-        frame.push_arg_slice(Rt::bind_slice(args, cx));
+        if let Some(tail_call) = self.tail_call.as_deref_mut().filter(|_| tail) {
+            if is_closure(func.bind(cx)) {
+                tail_call.push(Object::from(sym.bind(cx)));
+                tail_call.push(Object::from(func.bind(cx)));
+                for arg in args.iter() {
+                    tail_call.push(arg);
+                }
+                return Ok(NIL);
+            }
+        }
         let name = sym.bind(cx).name().to_owned();
-        func.call(frame, Some(&name), cx)
+        func.call_with_args(args, Some(&name), self.env, cx)
     }
 
     fn eval_function<'ob>(
@@ -327,11 +382,141 @@ impl Interpreter<'_, '_> {
         while self.eval_form(condition, cx)? != NIL {
             rooted_iter!(forms, &*body, cx);
             self.implicit_progn(forms, cx)?;
+            // Garbage from the body is unreachable between iterations
+            cx.garbage_collect(false);
         }
         Ok(NIL)
     }
 
-    fn eval_cond<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+    // (dolist (VAR LIST [RESULT]) BODY...)
+    fn dolist<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+        let (var, list, result, body) = Self::loop_spec(obj.bind(cx), sym::DOLIST, cx)?;
+        root!(var, cx);
+        root!(result, cx);
+        root!(body, cx);
+        root!(list, cx);
+        let list = rebind!(self.eval_form(list, cx)?);
+        rooted_iter!(elements, list, cx);
+        while let Some(elem) = elements.next()? {
+            self.eval_loop_body(var, elem, body, cx)?;
+        }
+        self.eval_form(result, cx)
+    }
+
+    // (dotimes (VAR COUNT [RESULT]) BODY...)
+    fn dotimes<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+        let (var, count, result, body) = Self::loop_spec(obj.bind(cx), sym::DOTIMES, cx)?;
+        root!(var, cx);
+        root!(result, cx);
+        root!(body, cx);
+        root!(count, cx);
+        let count: i64 = self.eval_form(count, cx)?.try_into()?;
+        root!(counter, NIL, cx);
+        for i in 0..count {
+            counter.set(i);
+            self.eval_loop_body(var, counter, body, cx)?;
+        }
+        // RESULT sees VAR bound to COUNT
+        let prev_len = self.vars.len();
+        let varbind_count = self.create_let_binding(var.bind(cx), count.into(), cx);
+        let value = match self.eval_form(result, cx) {
+            Ok(x) => Ok(rebind!(x, cx)),
+            Err(e) => Err(e),
+        };
+        self.vars.truncate(prev_len);
+        self.env.unbind(varbind_count, cx);
+        value
+    }
+
+    /// Whether the forms of a `cl-incf` or `cl-decf` update a plain variable,
+    /// as opposed to a generalized place that needs the macro.
+    fn is_variable_place(forms: Object) -> bool {
+        let Ok(mut forms) = forms.as_list() else { return false };
+        let place = forms.next();
+        let _delta = forms.next();
+        matches!(place, Some(Ok(x)) if matches!(x.untag(), ObjectType::Symbol(_)))
+            && forms.next().is_none()
+    }
+
+    /// `cl-incf` and `cl-decf` are macros in Emacs, but updating a variable
+    /// in place is common enough to do directly.
+    fn incf<'ob>(
+        &mut self,
+        obj: &Rto<Object>,
+        increment: bool,
+        cx: &'ob mut Context,
+    ) -> EvalResult<'ob> {
+        fn place(forms: Object) -> Result<Symbol, EvalError> {
+            Ok(forms.as_list()?.next().unwrap()?.try_into()?)
+        }
+        // The place is read before the delta is evaluated, like the macro
+        let value: Number = self.var_ref(place(obj.bind(cx))?, cx)?.try_into()?;
+        root!(value, cx);
+        let delta = match obj.bind(cx).as_list()?.nth(1).transpose()? {
+            Some(delta) => {
+                root!(delta, cx);
+                rebind!(self.eval_form(delta, cx)?)
+            }
+            None => 1.into(),
+        };
+        let delta: Number = delta.try_into()?;
+        let value = value.bind(cx);
+        let new = if increment {
+            crate::arith::add(&[value, delta], cx)?
+        } else {
+            crate::arith::sub(Some(value), &[delta], cx)?
+        };
+        let new = cx.add(new);
+        self.var_set(place(obj.bind(cx))?, new, cx)?;
+        Ok(new)
+    }
+
+    /// Split the forms of a `dolist` or `dotimes` into the loop variable, the
+    /// sequence or count form, the result form, and the body.
+    fn loop_spec<'ob>(
+        forms: Object<'ob>,
+        name: Symbol,
+        cx: &'ob Context,
+    ) -> Result<(Symbol<'ob>, Object<'ob>, Object<'ob>, Object<'ob>), EvalError> {
+        let ObjectType::Cons(forms) = forms.untag() else {
+            bail_err!(LispError::arg_cnt(name, 1, 0, cx))
+        };
+        let spec: Vec<_> = forms.car().as_list()?.collect::<Result<_, _>>()?;
+        let (var, value, result) = match spec[..] {
+            [var, value] => (var, value, NIL),
+            [var, value, result] => (var, value, result),
+            _ => bail_err!(LispError::arg_cnt(name, 2, spec.len() as u16, cx)),
+        };
+        let var: Symbol = var.try_into()?;
+        Ok((var, value, result, forms.cdr()))
+    }
+
+    /// Evaluate BODY with a fresh binding of VAR to VALUE, so closures created
+    /// in one iteration don't see the next.
+    fn eval_loop_body(
+        &mut self,
+        var: &Rto<Symbol>,
+        value: &Rto<Object>,
+        body: &Rto<Object>,
+        cx: &mut Context,
+    ) -> Result<(), EvalError> {
+        let prev_len = self.vars.len();
+        let varbind_count = self.create_let_binding(var.bind(cx), value.bind(cx), cx);
+        let result = (|| {
+            rooted_iter!(forms, body, cx);
+            self.implicit_progn(forms, cx).map(|_| ())
+        })();
+        self.vars.truncate(prev_len);
+        self.env.unbind(varbind_count, cx);
+        result
+    }
+
+    fn eval_cond<'ob>(
+        &mut self,
+        obj: &Rto<Object>,
+        tail: bool,
+        cx: &'ob mut Context,
+    ) -> EvalResult<'ob> {
         rooted_iter!(forms, obj, cx);
         while let Some(form) = forms.next()? {
             rooted_iter!(clause, form, cx);
@@ -341,6 +526,7 @@ impl Interpreter<'_, '_> {
                     return if clause.is_empty() {
                         Ok(rebind!(condition, cx))
                     } else {
+                        self.tail = tail;
                         self.implicit_progn(clause, cx)
                     };
                 }
@@ -349,10 +535,19 @@ impl Interpreter<'_, '_> {
         Ok(NIL)
     }
 
-    fn eval_and<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+    fn eval_and<'ob>(
+        &mut self,
+        obj: &Rto<Object>,
+        tail: bool,
+        cx: &'ob mut Context,
+    ) -> EvalResult<'ob> {
         root!(last, TRUE, cx);
         rooted_iter!(forms, obj, cx);
-        while let Some(form) = forms.next()? {
+        loop {
+            forms.advance()?;
+            let is_last = forms.is_empty();
+            let Some(form) = forms.get() else { break };
+            self.tail = tail && is_last;
             let result = self.eval_form(form, cx)?;
             if result == NIL {
                 return Ok(NIL);
@@ -362,9 +557,18 @@ impl Interpreter<'_, '_> {
         Ok(last.bind(cx))
     }
 
-    fn eval_or<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+    fn eval_or<'ob>(
+        &mut self,
+        obj: &Rto<Object>,
+        tail: bool,
+        cx: &'ob mut Context,
+    ) -> EvalResult<'ob> {
         rooted_iter!(forms, obj, cx);
-        while let Some(form) = forms.next()? {
+        loop {
+            forms.advance()?;
+            let is_last = forms.is_empty();
+            let Some(form) = forms.get() else { break };
+            self.tail = tail && is_last;
             let result = self.eval_form(form, cx)?;
             if result != NIL {
                 return Ok(rebind!(result, cx));
@@ -373,7 +577,12 @@ impl Interpreter<'_, '_> {
         Ok(NIL)
     }
 
-    fn eval_if<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
+    fn eval_if<'ob>(
+        &mut self,
+        obj: &Rto<Object>,
+        tail: bool,
+        cx: &'ob mut Context,
+    ) -> EvalResult<'ob> {
         rooted_iter!(forms, obj, cx);
         let Some(condition) = forms.next()? else {
             bail_err!(LispError::arg_cnt(sym::IF, 2, 0, cx))
@@ -383,14 +592,38 @@ impl Interpreter<'_, '_> {
             bail_err!(LispError::arg_cnt(sym::IF, 2, 1, cx))
         };
         root!(true_branch, cx);
-        #[expect(clippy::if_not_else)]
-        if self.eval_form(condition, cx)? != NIL {
+        let condition = self.eval_form(condition, cx)? != NIL;
+        self.tail = tail;
+        if condition {
             self.eval_form(true_branch, cx)
         } else {
             self.implicit_progn(forms, cx)
         }
     }
 
+    /// `when` and `unless` are macros in Emacs, but are common enough to
+    /// evaluate directly. `expected` is true for `when` and false for `unless`.
+    fn eval_when<'ob>(
+        &mut self,
+        obj: &Rto<Object>,
+        expected: bool,
+        tail: bool,
+        cx: &'ob mut Context,
+    ) -> EvalResult<'ob> {
+        rooted_iter!(forms, obj, cx);
+        let Some(condition) = forms.next()? else {
+            let name = if expected { sym::WHEN } else { sym::UNLESS };
+            bail_err!(LispError::arg_cnt(name, 1, 0, cx))
+        };
+        let condition = self.eval_form(condition, cx)? != NIL;
+        if condition == expected {
+            self.tail = tail;
+            self.implicit_progn(forms, cx)
+        } else {
+            Ok(NIL)
+        }
+    }
+
     fn setq<'ob>(&mut self, obj: &Rto<Object>, cx: &'ob mut Context) -> EvalResult<'ob> {
         rooted_iter!(forms, obj, cx);
         let mut arg_cnt = 0;
@@ -409,11 +642,8 @@ impl Interpreter<'_, '_> {
             }
             arg_cnt += 2;
         }
-        if arg_cnt < 2 {
-            Err(LispError::arg_cnt(sym::SETQ, 2, arg_cnt, cx).into())
-        } else {
-            Ok(last_value.bind(cx))
-        }
+        // (setq) with no arguments is valid and returns nil
+        Ok(last_value.bind(cx))
     }
 
     fn pairs<'ob>(
@@ -434,7 +664,7 @@ impl Interpreter<'_, '_> {
                 Some(value) => Ok(value),
                 None => match self.env.vars.get(sym) {
                     Some(v) => Ok(v.bind(cx)),
-                    None => Err(error!("Void variable: {sym}")),
+                    None => Err(LispError::void_variable(sym, cx).into()),
                 },
             }
         }
@@ -444,7 +674,7 @@ impl Interpreter<'_, '_> {
         let mut iter = self.vars.iter().rev();
         match iter.find(|cons| (cons.car(cx) == name)) {
             Some(value) => {
-                value.bind(cx).set_cdr(new_value).expect("variables should never be immutable");
+                value.set_cdr(new_value, cx).expect("variables should never be immutable");
                 Ok(())
             }
             None => self.env.set_var(name, new_value),
@@ -466,56 +696,59 @@ impl Interpreter<'_, '_> {
     ) -> EvalResult<'ob> {
         rooted_iter!(iter, form, cx);
         let prev_len = self.vars.len();
+        let prev_depth = self.env.binding_depth();
         // (let x ...)                   // (let)
         let Some(obj) = iter.next()? else { bail_err!(LispError::arg_cnt(sym::LET, 1, 0, cx)) };
-        let varbind_count = if parallel {
+        let bound = if parallel {
             self.let_bind_parallel(obj, cx)
         } else {
             self.let_bind_serial(obj, cx)
-        }?;
-        let obj = rebind!(self.implicit_progn(iter, cx)?);
-        // Remove old bindings
+        };
+        let result = match bound {
+            Ok(()) => match self.implicit_progn(iter, cx) {
+                Ok(x) => Ok(rebind!(x, cx)),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        // Remove old bindings, including when the body or a binding failed
         self.vars.truncate(prev_len);
-        self.env.unbind(varbind_count, cx);
-        Ok(obj)
+        let varbind_count = self.env.binding_depth() - prev_depth;
+        self.env.unbind(varbind_count.try_into().unwrap(), cx);
+        result
     }
 
-    fn let_bind_serial(&mut self, form: &Rto<Object>, cx: &mut Context) -> Result<u16, EvalError> {
-        let mut varbind_count = 0;
+    fn let_bind_serial(&mut self, form: &Rto<Object>, cx: &mut Context) -> Result<(), EvalError> {
         rooted_iter!(bindings, form, cx);
         while let Some(binding) = bindings.next()? {
             match binding.untag(cx) {
                 // (let ((x y)))
                 ObjectType::Cons(_) => {
-                    let cons = binding.as_cons();
+                    let cons = binding.as_cons().unwrap();
                     let val = rebind!(self.let_bind_value(cons, cx)?);
                     let var: Symbol =
                         cons.untag(cx).car().try_into().context("let variable must be a symbol")?;
-                    varbind_count += self.create_let_binding(var, val, cx);
+                    self.create_let_binding(var, val, cx);
                 }
                 // (let (x))
                 ObjectType::Symbol(sym) => {
-                    varbind_count += self.create_let_binding(sym, NIL, cx);
+                    self.create_let_binding(sym, NIL, cx);
                 }
                 // (let (1))
                 x => bail_err!(TypeError::new(Type::Cons, x)),
             }
         }
-        Ok(varbind_count)
+        Ok(())
     }
 
-    fn let_bind_parallel(
-        &mut self,
-        form: &Rto<Object>,
-        cx: &mut Context,
-    ) -> Result<u16, EvalError> {
+    fn let_bind_parallel(&mut self, form: &Rto<Object>, cx: &mut Context) -> Result<(), EvalError> {
         root!(let_bindings, new(Vec<(Slot<Symbol>, Slot<Object>)>), cx);
         rooted_iter!(bindings, form, cx);
         while let Some(binding) = bindings.next()? {
             match binding.untag(cx) {
                 // (let ((x y)))
                 ObjectType::Cons(_) => {
-                    let cons = binding.as_cons();
+                    let cons = binding.as_cons().unwrap();
                     let var = rebind!(self.let_bind_value(cons, cx)?);
                     let sym: Symbol =
                         cons.untag(cx).car().try_into().context("let variable must be a symbol")?;
@@ -529,11 +762,10 @@ impl Interpreter<'_, '_> {
                 x => bail_err!(TypeError::new(Type::Cons, x)),
             }
         }
-        let mut sum = 0;
         for (var, val) in let_bindings.bind_ref(cx) {
-            sum += self.create_let_binding(**var, **val, cx);
+            self.create_let_binding(**var, **val, cx);
         }
-        Ok(sum)
+        Ok(())
     }
 
     fn create_let_binding(&mut self, var: Symbol, val: Object, cx: &Context) -> u16 {
@@ -571,8 +803,13 @@ impl Interpreter<'_, '_> {
         mut forms: ElemStreamIter<'_>,
         cx: &'ob mut Context,
     ) -> EvalResult<'ob> {
+        let tail = std::mem::take(&mut self.tail);
         root!(last, NIL, cx);
-        while let Some(form) = forms.next()? {
+        loop {
+            forms.advance()?;
+            let is_last = forms.is_empty();
+            let Some(form) = forms.get() else { break };
+            self.tail = tail && is_last;
             let value = self.eval_form(form, cx)?;
             last.set(value);
         }
@@ -633,52 +870,18 @@ impl Interpreter<'_, '_> {
             Ok(x) => return Ok(rebind!(x, cx)),
             Err(e) => e,
         };
-        if matches!(err.error, ErrorType::Throw(_)) {
+        let Some(error) = err.condition(self.env, cx) else {
             return Err(err);
-        }
+        };
+        root!(error, cx);
         while let Some(handler) = forms.next()? {
             match handler.untag(cx) {
                 ObjectType::Cons(cons) => {
-                    // Check that conditions match
-                    let condition = cons.car();
-                    match condition.untag() {
-                        ObjectType::Symbol(sym::ERROR | sym::VOID_VARIABLE) => {}
-                        // TODO: Remove this once error handling is correctly implemented
-                        ObjectType::Symbol(s) if s.name() == "cl--generic-cyclic-definition" => {}
-                        ObjectType::Cons(conditions) => {
-                            for condition in conditions {
-                                let condition = condition?;
-                                // TODO: Handle different error symbols
-                                if condition != sym::DEBUG && condition != sym::ERROR {
-                                    bail_err!("non-error conditions {condition} not yet supported")
-                                }
-                            }
-                        }
-                        _ => bail_err!("Invalid condition handler: {condition}"),
+                    if !handles_error(cons.car(), error.car(cx), self.env, cx)? {
+                        continue;
                     }
-
                     // Call handlers with error
-                    let error = match err.error {
-                        ErrorType::Signal(id) => {
-                            let Some((sym, data)) = self.env.get_exception(id) else {
-                                unreachable!("Exception not found")
-                            };
-                            Cons::new(sym, data, cx)
-                        }
-                        ErrorType::Err(err) => {
-                            let err_str = format!("{err}");
-                            if let Ok(lisp_error) = err.downcast::<LispError>() {
-                                lisp_error.bind(cx)
-                            } else {
-                                // TODO: Need to remove the anyhow branch once
-                                // full errors are implemented
-                                Cons::new(sym::ERROR, err_str, cx)
-                            }
-                        }
-                        _ => unreachable!("Error type throw was not handled"),
-                    };
-
-                    let binding = Cons::new(var, error, cx);
+                    let binding = Cons::new(var, error.bind(cx), cx);
                     self.vars.push(binding);
                     let list: List = match cons.cdr().try_into() {
                         Ok(x) => x,
@@ -704,18 +907,55 @@ pub(crate) fn call_closure<'ob>(
     env: &mut Rt<Env>,
     cx: &'ob mut Context,
 ) -> EvalResult<'ob> {
-    cx.garbage_collect(false);
-    let closure: &Cons = closure.untag(cx);
-    match closure.car().untag() {
-        ObjectType::Symbol(sym::CLOSURE) => {
-            rooted_iter!(forms, closure.cdr(), cx);
-            let args = Rt::bind_slice(&env.stack[..arg_cnt], cx);
-            let vars = bind_variables(&mut forms, args, name, cx)?;
-            debug!("call vars: {vars:?}");
-            root!(vars, cx);
-            Interpreter { vars, env }.implicit_progn(forms, cx)
+    root!(func, Object::from(closure.bind(cx)), cx);
+    root!(args, new(Vec<Slot<Object>>), cx);
+    args.extend(&env.stack[..arg_cnt]);
+    root!(tail_call, new(Vec<Slot<Object>>), cx);
+    root!(result, NIL, cx);
+    let mut name = name.to_owned();
+    let mut tail_called = false;
+    // Calls to closures in tail position are returned to this loop instead of
+    // recursing, so tail recursive functions run in constant stack space. A
+    // closure that made a tail call has no frame left, so a backtrace only
+    // shows the closure that signaled the error and the original callee.
+    // Dynamic bindings are not affected, because the body of a `let` is never
+    // in tail position.
+    loop {
+        cx.garbage_collect(false);
+        let ObjectType::Cons(closure) = func.untag(cx) else { unreachable!() };
+        let ObjectType::Symbol(sym::CLOSURE) = closure.car().untag() else {
+            return Err(TypeError::new(Type::Func, closure.car()).into());
+        };
+        rooted_iter!(forms, closure.cdr(), cx);
+        let vars = bind_variables(&mut forms, Rt::bind_slice(args, cx), &name, cx)?;
+        debug!("call vars: {vars:?}");
+        root!(vars, cx);
+        tail_call.truncate(0);
+        let mut interpreter = Interpreter { vars, env, tail_call: Some(tail_call), tail: true };
+        let value = match interpreter.implicit_progn(forms, cx) {
+            Ok(value) => value,
+            // The frame of the original callee is added by `Function::call`
+            Err(e) if tail_called => return Err(e.add_trace(&name, args)),
+            Err(e) => return Err(e),
+        };
+        result.set(value);
+        if tail_call.is_empty() {
+            return Ok(result.bind(cx));
         }
-        other => Err(TypeError::new(Type::Func, other).into()),
+        // (name function args...)
+        let ObjectType::Symbol(sym) = tail_call[0].untag(cx) else { unreachable!() };
+        name = sym.name().to_owned();
+        tail_called = true;
+        func.set(&tail_call[1]);
+        args.truncate(0);
+        args.extend(&tail_call[2..]);
+    }
+}
+
+fn is_closure(func: Function) -> bool {
+    match func.untag() {
+        FunctionType::Cons(cons) => cons.car() == sym::CLOSURE,
+        _ => false,
     }
 }
 
@@ -900,6 +1140,32 @@ mod test {
         check_interpreter("(let ((x 1)) (setq x 2) x)", 2, cx);
         check_interpreter("(let* ())", false, cx);
         check_interpreter("(let* ((x 1) (y x)) y)", 1, cx);
+        check_interpreter("(let ((x 1) (y 2)) (+ x y))", 3, cx);
+        check_interpreter("(let ((x 1)) (let ((x 2) (y x)) y))", 1, cx);
+    }
+
+    #[test]
+    fn test_defvar() {
+        assert_lisp("(defvar defvar_test1 1)", "defvar_test1");
+        assert_lisp("(progn (defvar defvar_test2 1) (defvar defvar_test2 2) defvar_test2)", "1");
+        assert_lisp("(progn (setq defvar_test3 1) (defvar defvar_test3 (error \"evaluated\")) defvar_test3)", "1");
+        assert_lisp(
+            "(progn (defconst defvar_test4 1) (defconst defvar_test4 2) defvar_test4)",
+            "2",
+        );
+        assert_lisp("(progn (defvar defvar_test5 1) (defconst defvar_test5 2) defvar_test5)", "2");
+        assert_lisp(
+            "(progn (defvar defvar_test6 1) (defconst defvar_test7 1) (list (special-variable-p 'defvar_test6) (special-variable-p 'defvar_test7)))",
+            "(t t)",
+        );
+        assert_lisp(
+            "(progn (defvar defvar_test8 1 \"docs\") (get 'defvar_test8 'variable-documentation))",
+            "\"docs\"",
+        );
+        assert_lisp(
+            "(progn (defvar defvar_test9 1) (let ((defvar_test9 2)) (default-toplevel-value 'defvar_test9)))",
+            "1",
+        );
     }
 
     #[test]
@@ -926,6 +1192,28 @@ mod test {
             7,
             cx,
         );
+        check_interpreter(
+            "(progn (defvar dyn_test8 1) (makunbound 'dyn_test8) (let ((dyn_test8 7))) (boundp 'dyn_test8))",
+            false,
+            cx,
+        );
+        // bindings are undone when the body signals
+        check_interpreter(
+            "(progn (defvar dyn_test9 1) (condition-case nil (let ((dyn_test9 2)) (error \"x\")) (error nil)) dyn_test9)",
+            1,
+            cx,
+        );
+        // or when a later let* binding signals
+        check_interpreter(
+            "(progn (defvar dyn_test10 1) (condition-case nil (let* ((dyn_test10 2) (y (error \"x\"))) y) (error nil)) dyn_test10)",
+            1,
+            cx,
+        );
+        check_interpreter(
+            "(progn (defvar dyn_test11 1) (let ((fn #'(lambda () dyn_test11))) (let ((dyn_test11 5)) (funcall fn))))",
+            5,
+            cx,
+        );
         check_interpreter("(eq (make-symbol \"bar\") 'bar)", false, cx);
         check_interpreter(
             "(let ((x (make-symbol \"x\"))) (put x 'p t) (garbage-collect) (get x 'p))",
@@ -952,6 +1240,14 @@ mod test {
         check_interpreter("(or nil)", false, cx);
         check_interpreter("(or nil 1)", 1, cx);
         check_interpreter("(or 1 2)", 1, cx);
+        check_interpreter("(and 1 2 3)", 3, cx);
+        check_interpreter("(and 1 nil 3)", false, cx);
+        check_interpreter("(or nil 2 3)", 2, cx);
+        // Forms after the deciding one are not evaluated
+        check_interpreter("(let ((x 0)) (and nil (setq x 1)) x)", 0, cx);
+        check_interpreter("(let ((x 0)) (or 1 (setq x 1)) x)", 0, cx);
+        check_interpreter("(let ((x 0)) (and (setq x 1) (setq x 2)) x)", 2, cx);
+        check_interpreter("(or nil (and 1 nil) (and 2 3))", 3, cx);
         check_interpreter("(cond)", false, cx);
         check_interpreter("(cond nil)", false, cx);
         check_interpreter("(cond (1))", 1, cx);
@@ -970,6 +1266,96 @@ mod test {
             cx,
         );
         check_interpreter("(let ((i 3) (x 0)) (while (progn (setq x (1- x)) (> i 0)) (setq x (+ x i) i (1- i) )) x)", 2, cx);
+        check_interpreter(
+            "(let ((i 1) (sum 0)) (while (<= i 100) (setq sum (+ sum i) i (1+ i))) sum)",
+            5050,
+            cx,
+        );
+        check_interpreter("(let ((i 0)) (while (< i 3) (setq i (1+ i))))", false, cx);
+        // Values allocated in one iteration and used in the next survive
+        // collection
+        check_interpreter(
+            "(let ((i 0) (acc nil)) (while (< i 50) (setq acc (cons (format \"%d\" i) acc) i (1+ i))) (car acc))",
+            "49",
+            cx,
+        );
+    }
+
+    #[test]
+    fn test_dolist_dotimes() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        check_interpreter("(let ((n 0)) (dotimes (i 3) (setq n (1+ n))) n)", 3, cx);
+        check_interpreter("(let ((sum 0)) (dotimes (i 4 sum) (setq sum (+ sum i))))", 6, cx);
+        check_interpreter("(dotimes (i 3 i))", 3, cx);
+        check_interpreter("(dotimes (i 0 \"done\") (error \"unreachable\"))", "done", cx);
+        check_interpreter(
+            "(let ((acc nil)) (dolist (x '(1 2 3)) (setq acc (cons x acc))) (equal acc '(3 2 1)))",
+            true,
+            cx,
+        );
+        check_interpreter(
+            "(let ((acc nil)) (equal (dolist (x '(1 2 3) acc) (setq acc (cons (* x x) acc))) '(9 4 1)))",
+            true,
+            cx,
+        );
+        check_interpreter("(dolist (x nil) (error \"unreachable\"))", false, cx);
+        // Each iteration gets its own binding
+        check_interpreter(
+            "(let ((fns nil)) (dolist (x '(1 2)) (setq fns (cons #'(lambda () x) fns))) (equal (mapcar #'funcall fns) '(2 1)))",
+            true,
+            cx,
+        );
+        check_interpreter("(let ((x \"outer\")) (dolist (x '(1 2)) x) x)", "outer", cx);
+        check_error("(dolist (x))", cx);
+        check_error("(dolist (1 '(1)))", cx);
+        check_error("(dotimes (i 'a))", cx);
+    }
+
+    #[test]
+    fn test_cond() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        check_interpreter("(cond (nil 1) (t 2) (t 3))", 2, cx);
+        check_interpreter("(cond (nil 1) (5))", 5, cx);
+        check_interpreter("(cond (nil 1) ((eq 1 2) 2))", false, cx);
+        check_interpreter("(cond)", false, cx);
+        check_interpreter("(cond () (t 1))", 1, cx);
+        // later clauses are not evaluated
+        check_interpreter("(let ((x 0)) (cond ((setq x 1)) ((setq x 2))) x)", 1, cx);
+        check_interpreter("(let ((x 0)) (cond (nil (setq x 1)) (t (setq x 2) 3)))", 3, cx);
+        check_error("(cond 1)", cx);
+    }
+
+    #[test]
+    fn test_when_unless() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        check_interpreter("(when t 1 2)", 2, cx);
+        check_interpreter("(when nil 1)", false, cx);
+        check_interpreter("(when t)", false, cx);
+        check_interpreter("(unless nil 3)", 3, cx);
+        check_interpreter("(unless t 3)", false, cx);
+        check_interpreter("(let ((x 0)) (when (setq x 1) (setq x (1+ x))) x)", 2, cx);
+        check_interpreter("(let ((x 0)) (unless (setq x 1) (setq x 5)) x)", 1, cx);
+        check_error("(when)", cx);
+    }
+
+    #[test]
+    fn test_incf() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        check_interpreter("(progn (setq incf-x 1) (cl-incf incf-x) incf-x)", 2, cx);
+        check_interpreter("(progn (setq incf-x 1) (cl-incf incf-x 5))", 6, cx);
+        check_interpreter("(progn (setq incf-x 1) (cl-decf incf-x 3) incf-x)", -2, cx);
+        check_interpreter("(let ((x 1)) (cl-incf x 0.5) x)", 1.5, cx);
+        check_interpreter("(let ((x 1)) (cl-decf x) x)", 0, cx);
+        // the variable is read before the increment is evaluated
+        check_interpreter("(let ((x 1)) (cl-incf x (setq x 10)))", 11, cx);
+        // leaving the fixnum range signals an overflow-error
+        check_error("(let ((x 36028797018963967)) (cl-incf x))", cx);
+        check_error("(let ((x 'a)) (cl-incf x))", cx);
+        check_error("(let ((x 1)) (cl-incf x 'a))", cx);
     }
 
     #[test]
@@ -984,6 +1370,69 @@ mod test {
         check_interpreter("(if 1 2 3)", 2, cx);
         check_interpreter("(if nil 2 3)", 3, cx);
         check_interpreter("(if (and 1 nil) 2 3)", 3, cx);
+        check_interpreter("(progn (setq setq_test 5) setq_test)", 5, cx);
+        check_interpreter("(setq setq_test1 1 setq_test2 2)", 2, cx);
+        check_interpreter(
+            "(progn (setq setq_test3 1 setq_test3 (1+ setq_test3)) setq_test3)",
+            2,
+            cx,
+        );
+        check_interpreter("(setq)", false, cx);
+        check_error("(setq setq_test4)", cx);
+        check_error("(setq 1 2)", cx);
+    }
+
+    #[test]
+    fn test_eval() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        check_interpreter("(eval '(+ 1 2))", 3, cx);
+        let x: Object = intern("x", cx).into();
+        root!(x, cx);
+        check_interpreter("(eval ''x)", x, cx);
+        check_interpreter("(eval '(if nil 1 (quote 2)))", 2, cx);
+        check_interpreter("(eval 'x '((x . 5)))", 5, cx);
+        check_interpreter("(let ((y 2)) (eval '(* y 3) (list (cons 'y y))))", 6, cx);
+    }
+
+    #[test]
+    fn test_tail_calls() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        check_interpreter(
+            "(progn (defalias 'tail-count #'(lambda (n acc) (if (= n 0) acc (tail-count (1- n) (1+ acc))))) (tail-count 10000 0))",
+            10_000,
+            cx,
+        );
+        // tail position is kept through cond, progn, and, and or
+        check_interpreter(
+            "(progn (defalias 'tail-cond #'(lambda (n) (cond ((= n 0) 7) (t (progn (and t (or nil (tail-cond (1- n))))))))) (tail-cond 10000))",
+            7,
+            cx,
+        );
+        // mutual recursion
+        check_interpreter(
+            "(progn (defalias 'tail-even #'(lambda (n) (if (= n 0) t (tail-odd (1- n))))) (defalias 'tail-odd #'(lambda (n) (if (= n 0) nil (tail-even (1- n))))) (tail-even 10001))",
+            false,
+            cx,
+        );
+        // a special variable bound around a call is unbound when it returns
+        check_interpreter(
+            "(progn (defvar tail-special 0) (defalias 'tail-dyn #'(lambda (n) (if (= n 0) tail-special (let ((tail-special n)) (tail-dyn (1- n)))))) (let ((result (tail-dyn 3))) (and (= tail-special 0) result)))",
+            1,
+            cx,
+        );
+        check_interpreter(
+            "(progn (defvar tail-special 0) (defalias 'tail-bind #'(lambda (n) (if (= n 0) tail-special (let ((tail-special (+ tail-special n))) (tail-bind-next (1- n)))))) (defalias 'tail-bind-next #'(lambda (n) (tail-bind n))) (let ((result (tail-bind 100))) (and (= tail-special 0) result)))",
+            5050,
+            cx,
+        );
+        // calls that are not in tail position still return to their caller
+        check_interpreter(
+            "(progn (defalias 'tail-sum #'(lambda (n) (if (= n 0) 0 (+ n (tail-sum (1- n)))))) (tail-sum 100))",
+            5050,
+            cx,
+        );
     }
 
     #[test]
@@ -1033,6 +1482,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_closures() {
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        // the captured environment outlives the let and survives collection
+        check_interpreter(
+            "(let ((f (let ((x 5)) #'(lambda () x)))) (garbage-collect) (funcall f))",
+            5,
+            cx,
+        );
+        check_interpreter(
+            "(let ((counter (let ((n 0)) #'(lambda () (setq n (1+ n)))))) (funcall counter) (garbage-collect) (funcall counter))",
+            2,
+            cx,
+        );
+        check_interpreter(
+            "(progn (defalias 'make-adder #'(lambda (n) #'(lambda (x) (+ x n)))) (funcall (make-adder 3) 4))",
+            7,
+            cx,
+        );
+    }
+
     #[test]
     fn test_call() {
         let roots = &RootSet::default();
@@ -1105,6 +1576,29 @@ mod test {
         check_error("(condition-case nil (if) 5 (error 7))", cx);
     }
 
+    #[test]
+    fn test_condition_handlers() {
+        assert_lisp(
+            "(condition-case nil (signal 'arith-error nil) (arith-error 'caught))",
+            "caught",
+        );
+        assert_lisp("(condition-case nil (/ 1 0) (void-variable 1) (arith-error 2))", "2");
+        assert_lisp("(condition-case nil (/ 1 0) ((void-variable arith-error) 3))", "3");
+        assert_lisp("(condition-case nil (signal 'arith-error nil) (t 4))", "4");
+        assert_lisp(
+            "(condition-case nil (condition-case nil (/ 1 0) (void-variable 1)) (arith-error 2))",
+            "2",
+        );
+        assert_lisp(
+            "(condition-case err void_var_test (void-variable err))",
+            "(void-variable void_var_test)",
+        );
+        assert_lisp(
+            "(progn (put 'my-error 'error-conditions '(my-error my-parent error)) (condition-case nil (signal 'my-error nil) (my-parent 'parent)))",
+            "parent",
+        );
+    }
+
     #[test]
     fn test_throw_catch() {
         let roots = &RootSet::default();
@@ -1121,4 +1615,13 @@ mod test {
         check_error("(throw 1 2)", cx);
         check_error("(catch 2 (throw 3 4))", cx);
     }
+
+    #[test]
+    fn test_no_catch() {
+        assert_lisp("(catch 'x (throw 'x 5))", "5");
+        assert_lisp("(let ((tag 'x)) (catch tag (throw 'x (+ 2 3))))", "5");
+        assert_lisp("(catch 'x (catch 'y (throw 'x 1)) 2)", "1");
+        assert_lisp("(condition-case err (throw 'x 5) (error err))", "(no-catch x 5)");
+        assert_lisp("(condition-case err (catch 'y (throw 'x 5)) (error (car err)))", "no-catch");
+    }
 }
@@ -4,8 +4,8 @@ use crate::core::env::{sym, Env};
 use crate::core::error::{Type, TypeError};
 use crate::core::gc::{Context, Rt, Rto};
 use crate::core::object::{
-    Function, Gc, LispString, Object, ObjectType, OptionalFlag, Symbol, TagType, WithLifetime, NIL,
-    TRUE,
+    Function, Gc, LispString, LispVec, List, Object, ObjectType, OptionalFlag, RecordBuilder,
+    Symbol, TagType, WithLifetime, NIL, TRUE,
 };
 use crate::reader;
 use crate::{interpreter, rooted_iter};
@@ -60,6 +60,15 @@ pub(crate) fn read_from_string<'ob>(
     Ok(Cons::new(obj, new_pos as i64, cx).into())
 }
 
+#[defun]
+pub(crate) fn read<'ob>(stream: Option<Object<'ob>>, cx: &'ob Context) -> Result<Object<'ob>> {
+    match stream.map(|x| x.untag()) {
+        Some(ObjectType::String(string)) => Ok(reader::read(string, cx)?.0),
+        // TODO: support reading from buffers, markers and functions
+        _ => bail!("read is only supported for strings"),
+    }
+}
+
 pub(crate) fn load_internal(contents: &str, cx: &mut Context, env: &mut Rt<Env>) -> Result<bool> {
     let mut pos = 0;
     let macroexpand: Option<Function> = None;
@@ -205,31 +214,175 @@ pub(crate) fn load(
     result
 }
 
+/// Where a symbol is interned. The global obarray is the value of the variable
+/// `obarray`, a record of type `obarray` (see [`global_obarray`]). Other
+/// obarrays are vectors (see `obarray-make`). Each slot of the vector holds a
+/// list of the symbols whose names hash to it, or 0 if nothing has been
+/// interned there yet.
+#[derive(Copy, Clone)]
+enum Obarray<'ob> {
+    Global,
+    Vector(&'ob LispVec),
+}
+
+impl<'ob> Obarray<'ob> {
+    /// Resolve the OBARRAY argument of the intern functions. If it is nil, use
+    /// the value of the variable `obarray`.
+    fn new(obarray: Option<Object<'ob>>, env: &Rt<Env>, cx: &'ob Context) -> Result<Self> {
+        let obarray = match obarray {
+            Some(obarray) if !obarray.is_nil() => obarray,
+            _ => match env.vars.get(sym::OBARRAY) {
+                Some(value) => value.bind(cx),
+                None => return Ok(Obarray::Global),
+            },
+        };
+        match obarray.untag() {
+            ObjectType::Record(record)
+                if record.first().is_some_and(|x| x.get() == sym::OBARRAY) =>
+            {
+                Ok(Obarray::Global)
+            }
+            ObjectType::Vec(vec) if !vec.is_empty() => Ok(Obarray::Vector(vec)),
+            _ => Err(TypeError::new(Type::Vec, obarray).into()),
+        }
+    }
+
+    fn bucket(vec: &LispVec, name: &str) -> usize {
+        let hash = name.bytes().fold(0usize, |h, b| h.wrapping_mul(31).wrapping_add(b.into()));
+        hash % vec.len()
+    }
+
+    /// The symbols in the bucket for `name`.
+    fn bucket_list(vec: &'ob LispVec, name: &str) -> List<'ob> {
+        // Unused buckets hold 0
+        vec[Self::bucket(vec, name)].get().try_into().unwrap_or_default()
+    }
+
+    fn lookup(self, name: &str) -> Option<Symbol<'ob>> {
+        match self {
+            Obarray::Global => {
+                let map = crate::core::env::INTERNED_SYMBOLS.lock().unwrap();
+                map.get(name).map(|x| unsafe { x.with_lifetime() })
+            }
+            Obarray::Vector(vec) => Self::bucket_list(vec, name)
+                .elements()
+                .filter_map(|x| Symbol::try_from(x.ok()?).ok())
+                .find(|x| x.name() == name),
+        }
+    }
+}
+
+/// The initial value of the variable `obarray`. The symbols themselves live
+/// in the global symbol map, so this is only a handle that [`Obarray`]
+/// recognizes.
+pub(crate) fn global_obarray<'ob>(cx: &'ob Context) -> RecordBuilder<'ob> {
+    let mut record = cx.vec_with_capacity(1);
+    record.push(sym::OBARRAY.into());
+    RecordBuilder(record)
+}
+
+fn symbol_name<'ob>(string: Object<'ob>) -> Result<(&'ob str, Option<Symbol<'ob>>)> {
+    match string.untag() {
+        ObjectType::Symbol(sym) => Ok((sym.get().name(), Some(sym))),
+        ObjectType::String(string) => Ok((string, None)),
+        x => Err(TypeError::new(Type::String, x).into()),
+    }
+}
+
 #[defun]
-pub(crate) fn intern<'ob>(string: &str, cx: &'ob Context) -> Symbol<'ob> {
-    crate::core::env::intern(string, cx)
+pub(crate) fn intern<'ob>(
+    string: &str,
+    obarray: Option<Object<'ob>>,
+    env: &Rt<Env>,
+    cx: &'ob Context,
+) -> Result<Symbol<'ob>> {
+    let obarray = Obarray::new(obarray, env, cx)?;
+    if let Some(sym) = obarray.lookup(string) {
+        return Ok(sym);
+    }
+    match obarray {
+        Obarray::Global => Ok(crate::core::env::intern(string, cx)),
+        Obarray::Vector(vec) => {
+            let sym = Symbol::new_uninterned(string, cx);
+            let bucket = Obarray::bucket_list(vec, string);
+            vec.try_mut()?[Obarray::bucket(vec, string)].set(Cons::new(sym, bucket, cx).into());
+            Ok(sym)
+        }
+    }
 }
 
 #[defun]
-pub(crate) fn intern_soft(string: Object, obarray: OptionalFlag) -> Result<Symbol> {
-    ensure!(obarray.is_none(), "intern-soft obarray not implemented");
-    match string.untag() {
-        ObjectType::Symbol(sym) => {
-            if sym.interned() {
-                Ok(sym)
-            } else {
-                Ok(sym::NIL)
-            }
+pub(crate) fn intern_soft<'ob>(
+    string: Object<'ob>,
+    obarray: Option<Object<'ob>>,
+    env: &Rt<Env>,
+    cx: &'ob Context,
+) -> Result<Symbol<'ob>> {
+    let (name, symbol) = symbol_name(string)?;
+    let found = Obarray::new(obarray, env, cx)?.lookup(name);
+    // A symbol argument is only found if it is that exact symbol
+    match (found, symbol) {
+        (Some(found), Some(symbol)) if found != symbol => Ok(sym::NIL),
+        (Some(found), _) => Ok(found),
+        (None, _) => Ok(sym::NIL),
+    }
+}
+
+#[defun]
+fn unintern<'ob>(
+    name: Object<'ob>,
+    obarray: Option<Object<'ob>>,
+    env: &Rt<Env>,
+    cx: &'ob Context,
+) -> Result<bool> {
+    let (string, symbol) = symbol_name(name)?;
+    let obarray = Obarray::new(obarray, env, cx)?;
+    let Some(found) = obarray.lookup(string) else { return Ok(false) };
+    if symbol.is_some_and(|x| x != found) {
+        return Ok(false);
+    }
+    match obarray {
+        Obarray::Global => {
+            crate::core::env::INTERNED_SYMBOLS.lock().unwrap().remove(string);
+        }
+        Obarray::Vector(vec) => {
+            let bucket = crate::fns::delq(found.into(), Obarray::bucket_list(vec, string))?;
+            vec.try_mut()?[Obarray::bucket(vec, string)].set(bucket);
         }
-        ObjectType::String(string) => {
+    }
+    Ok(true)
+}
+
+#[defun]
+fn mapatoms<'ob>(
+    function: &Rto<Function>,
+    obarray: Option<&Rto<Object>>,
+    env: &mut Rt<Env>,
+    cx: &'ob mut Context,
+) -> Result<Object<'ob>> {
+    let symbols: Vec<Object> = match Obarray::new(obarray.map(|x| x.bind(cx)), env, cx)? {
+        Obarray::Global => {
             let map = crate::core::env::INTERNED_SYMBOLS.lock().unwrap();
-            match map.get(string) {
-                Some(sym) => Ok(unsafe { sym.with_lifetime() }),
-                None => Ok(sym::NIL),
+            map.symbols().map(Into::into).collect()
+        }
+        Obarray::Vector(vec) => {
+            let mut symbols = Vec::new();
+            for bucket in vec.iter() {
+                if let Ok(list) = List::try_from(bucket.get()) {
+                    for sym in list {
+                        symbols.push(sym?);
+                    }
+                }
             }
+            symbols
         }
-        x => Err(TypeError::new(Type::String, x).into()),
+    };
+    root!(symbols, cx);
+    for i in 0..symbols.len() {
+        let sym = symbols[i].bind(cx);
+        call!(function, sym; env, cx)?;
     }
+    Ok(NIL)
 }
 
 defsym!(INTERNAL_MACROEXPAND_FOR_LOAD);
@@ -241,6 +394,7 @@ defvar!(LOAD_FILE_NAME);
 defvar!(BYTE_BOOLEAN_VARS);
 defvar!(MACROEXP__DYNVARS);
 defvar!(AFTER_LOAD_ALIST);
+defvar!(OBARRAY, crate::lread::global_obarray(cx));
 
 #[cfg(test)]
 mod test {
@@ -262,4 +416,63 @@ mod test {
         let val = interpreter::eval(obj, None, env, cx).unwrap();
         assert_eq!(val, 4.5);
     }
+
+    #[test]
+    fn test_read() {
+        use crate::interpreter::assert_lisp;
+        assert_lisp("(read \"(+ 1 2)\")", "(+ 1 2)");
+        assert_lisp("(read \"3.14\")", "3.14");
+        assert_lisp("(read \"(a \\\"b\\\" . 1)\")", "(a \"b\" . 1)");
+        assert_lisp("(read \"'x\")", "(quote x)");
+        assert_lisp("(car (read-from-string \"foo bar\"))", "foo");
+    }
+
+    #[test]
+    fn test_obarray() {
+        use crate::interpreter::assert_lisp;
+        assert_lisp(
+            r#"(let* ((ob (make-vector 7 0)) (sym (intern "private-sym" ob))) (list (symbol-name sym) (eq sym (intern-soft "private-sym" ob)) (intern-soft "private-sym")))"#,
+            r#"("private-sym" t nil)"#,
+        );
+        assert_lisp(r#"(let ((ob (make-vector 3 0))) (eq (intern "a" ob) (intern "a" ob)))"#, "t");
+        assert_lisp(r#"(let ((ob (make-vector 3 0))) (eq (intern "a" ob) 'a))"#, "nil");
+        assert_lisp(
+            r#"(let ((ob (make-vector 3 0))) (intern "a" ob) (list (unintern "a" ob) (unintern "a" ob) (intern-soft "a" ob)))"#,
+            "(t nil nil)",
+        );
+        assert_lisp(
+            r#"(let ((ob (make-vector 2 0)) (names nil)) (intern "x" ob) (intern "y" ob) (intern "z" ob) (mapatoms #'(lambda (s) (setq names (cons (symbol-name s) names))) ob) (sort names #'string-lessp))"#,
+            r#"("x" "y" "z")"#,
+        );
+        assert_lisp(r#"(intern-soft "car")"#, "car");
+        assert_lisp(r#"(intern-soft (make-symbol "car"))"#, "nil");
+        assert_lisp(
+            r#"(progn (intern "lread-unintern-test") (list (unintern "lread-unintern-test" nil) (intern-soft "lread-unintern-test")))"#,
+            "(t nil)",
+        );
+    }
+
+    #[test]
+    fn test_global_obarray() {
+        use crate::core::env::init_variables;
+        let roots = &RootSet::default();
+        let cx = &mut Context::new(roots);
+        sym::init_symbols();
+        root!(env, new(Env), cx);
+        init_variables(cx, env);
+        let tests = [
+            (r#"(eq (intern-soft "car" obarray) 'car)"#, true),
+            (r#"(eq (intern "car" obarray) 'car)"#, true),
+            (r#"(intern-soft "lread-not-interned" obarray)"#, false),
+            ("(eq (type-of obarray) 'obarray)", true),
+            // binding obarray to a vector changes the default
+            (r#"(let ((obarray (make-vector 3 0))) (intern-soft "car"))"#, false),
+        ];
+        for (expr, expect) in tests {
+            let obj = reader::read(expr, cx).unwrap().0;
+            root!(obj, cx);
+            let value = rebind!(interpreter::eval(obj, None, env, cx).unwrap(), cx);
+            assert_eq!(value, expect, "{expr}");
+        }
+    }
 }
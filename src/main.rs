@@ -13,6 +13,7 @@ mod character;
 mod chartab;
 mod data;
 mod dired;
+mod doc;
 mod editfns;
 mod emacs;
 mod eval;
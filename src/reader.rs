@@ -1,4 +1,5 @@
 //! Lisp reader that reads an object from a string.
+use crate::arith::NumberValue;
 use crate::core::{
     env::{intern, sym},
     gc::Context,
@@ -309,12 +310,61 @@ fn intern_symbol<'ob>(symbol: &str, cx: &'ob Context) -> Symbol<'ob> {
 /// Parse a symbol from a string. This will either by a true symbol or a number
 /// literal.
 fn parse_symbol<'a>(slice: &str, cx: &'a Context) -> Object<'a> {
-    match slice.parse::<i64>() {
-        Ok(num) => cx.add(num),
-        Err(_) => match slice.parse::<f64>() {
-            Ok(num) => cx.add(num),
-            Err(_) => cx.add(intern_symbol(slice, cx)),
-        },
+    match parse_number(slice) {
+        Some(num) => cx.add(num),
+        None => cx.add(intern_symbol(slice, cx)),
+    }
+}
+
+/// Parse `slice` as a number literal using the same grammar as Emacs. A
+/// trailing dot is allowed on integers, floats need digits after the dot or an
+/// exponent, and infinity and NaN are spelled `1.0e+INF` and `0.0e+NaN`.
+/// Anything else (including Rust spellings like `inf`) is not a number.
+pub(crate) fn parse_number(slice: &str) -> Option<NumberValue> {
+    fn digits(s: &str) -> usize {
+        s.bytes().take_while(u8::is_ascii_digit).count()
+    }
+    let unsigned = slice.strip_prefix(['+', '-']).unwrap_or(slice);
+    let lead = digits(unsigned);
+    let mut rest = &unsigned[lead..];
+    let dot = rest.starts_with('.');
+    if dot {
+        rest = &rest[1..];
+    }
+    let trail = digits(rest);
+    rest = &rest[trail..];
+    let mut exponent = false;
+    let mut special = None;
+    if let Some(exp) = rest.strip_prefix(['e', 'E']) {
+        let exp_unsigned = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+        let exp_digits = digits(exp_unsigned);
+        if exp_digits > 0 {
+            (exponent, rest) = (true, &exp_unsigned[exp_digits..]);
+        } else if let Some(tail) = exp.strip_prefix("+INF") {
+            (exponent, special, rest) = (true, Some(f64::INFINITY), tail);
+        } else if let Some(tail) = exp.strip_prefix("+NaN") {
+            (exponent, special, rest) = (true, Some(f64::NAN), tail);
+        }
+    }
+    if !rest.is_empty() {
+        return None;
+    }
+    let negative = slice.starts_with('-');
+    if lead > 0 && trail == 0 && !exponent {
+        let int = &slice[..slice.len() - usize::from(dot)];
+        // integers too large for a fixnum are read as floats
+        return match int.parse::<i64>() {
+            Ok(num) => Some(NumberValue::Int(num)),
+            Err(_) => int.parse().ok().map(NumberValue::Float),
+        };
+    }
+    if trail == 0 && !(lead > 0 && exponent) {
+        return None;
+    }
+    match special {
+        Some(val) if negative => Some(NumberValue::Float(-val)),
+        Some(val) => Some(NumberValue::Float(val)),
+        None => slice.parse().ok().map(NumberValue::Float),
     }
 }
 
@@ -558,6 +608,14 @@ mod test {
         check_reader!(-105, "-105", cx);
         check_reader!(1.5, "1.5", cx);
         check_reader!(-3.0, "-3.0", cx);
+        check_reader!(0.5, ".5", cx);
+        check_reader!(-0.5, "-.5", cx);
+        check_reader!(1000.0, "1e3", cx);
+        check_reader!(1000.0, "1.e3", cx);
+        check_reader!(0.015, "1.5e-2", cx);
+        check_reader!(1, "1.", cx);
+        check_reader!(f64::INFINITY, "1.0e+INF", cx);
+        check_reader!(f64::NEG_INFINITY, "-1.0e+INF", cx);
         check_reader!(1, "+1", cx);
         check_reader!(1, "001", cx);
         check_reader!(1, "#o001", cx);
@@ -611,6 +669,11 @@ mod test {
         check_reader!(intern("--1", cx), "--1", cx);
         check_reader!(intern("1", cx), "\\1", cx);
         check_reader!(intern("3.0.0", cx), "3.0.0", cx);
+        check_reader!(intern("inf", cx), "inf", cx);
+        check_reader!(intern("nan", cx), "nan", cx);
+        check_reader!(intern("-infinity", cx), "-infinity", cx);
+        check_reader!(intern("1.5e", cx), "1.5e", cx);
+        check_reader!(intern("1e+INF", cx), "\\1e+INF", cx);
         check_reader!(intern("1+", cx), "1+", cx);
         check_reader!(intern("+1", cx), "\\+1", cx);
         check_reader!(intern(" x", cx), "\\ x", cx);
@@ -22,23 +22,39 @@ fn string_match<'ob>(
     // TODO: implement inhibit-modify
     let re = Regex::new(&lisp_regex_to_rust(regexp))?;
 
-    let start = start.unwrap_or(0) as usize;
-    if let Some(matches) = re.captures_iter(&string[start..]).next() {
-        let mut all: Vec<Object> = Vec::new();
-        let matches = matches?;
-        let mut groups = matches.iter();
-        // TODO: match data should be char position, not byte
-        while let Some(Some(group)) = groups.next() {
-            all.push(group.start().into());
-            all.push(group.end().into());
+    let len = string.chars().count() as i64;
+    let start = match start.unwrap_or(0) {
+        x if x < 0 => len + x,
+        x => x,
+    };
+    ensure!((0..=len).contains(&start), "Args out of range: {string:?}, {start}");
+    let Some(matches) = re.captures_from_pos(string, char_to_byte(string, start as usize))? else {
+        return Ok(NIL);
+    };
+    let char_pos = |byte: usize| Object::from(string[..byte].chars().count() as i64);
+    let mut all: Vec<Object> = Vec::new();
+    for group in matches.iter() {
+        match group {
+            Some(group) => {
+                all.push(char_pos(group.start()));
+                all.push(char_pos(group.end()));
+            }
+            None => all.extend([NIL, NIL]),
         }
-        let match_data = crate::fns::slice_into_list(&all, None, cx);
-        env.match_data.set(match_data);
-        let head: &Cons = match_data.try_into().unwrap();
-        Ok(head.car())
-    } else {
-        Ok(NIL)
     }
+    // unmatched groups at the end are left out of the match data
+    while all.last().is_some_and(|x| x.is_nil()) {
+        all.pop();
+    }
+    let match_data = crate::fns::slice_into_list(&all, None, cx);
+    env.match_data.set(match_data);
+    let head: &Cons = match_data.try_into().unwrap();
+    Ok(head.car())
+}
+
+/// Convert a character index into `string` to a byte offset.
+fn char_to_byte(string: &str, pos: usize) -> usize {
+    string.char_indices().nth(pos).map_or(string.len(), |(idx, _)| idx)
 }
 
 #[defun]
@@ -64,9 +80,8 @@ fn replace_match(
     let Some(beg) = match_data.next()? else { bail!(sub_err()) };
     let Some(end) = match_data.next()? else { bail!(sub_err()) };
 
-    // TODO: match data should be char position, not byte
-    let beg: usize = beg.try_into()?;
-    let end: usize = end.try_into()?;
+    let beg = char_to_byte(string, beg.try_into()?);
+    let end = char_to_byte(string, end.try_into()?);
 
     // replace the range beg..end in string with newtext
     let mut new_string = String::new();
@@ -148,13 +163,13 @@ fn set_match_data<'ob>(list: List, _reseat: OptionalFlag, env: &mut Rt<Env>) ->
 #[defun]
 fn match_beginning<'ob>(subexp: usize, env: &Rt<Env>, cx: &'ob Context) -> Result<Object<'ob>> {
     let list = env.match_data.bind(cx).as_list()?;
-    Ok(list.fallible().nth(subexp)?.unwrap_or_default())
+    Ok(list.fallible().nth(subexp * 2)?.unwrap_or_default())
 }
 
 #[defun]
 fn match_end<'ob>(subexp: usize, env: &Rt<Env>, cx: &'ob Context) -> Result<Object<'ob>> {
     let list = env.match_data.bind(cx).as_list()?;
-    Ok(list.fallible().nth(subexp + 1)?.unwrap_or_default())
+    Ok(list.fallible().nth(subexp * 2 + 1)?.unwrap_or_default())
 }
 
 #[defun]
@@ -202,4 +217,73 @@ mod test {
         let result = replace_match(newtext, None, None, Some(string), None, env, cx).unwrap();
         assert_eq!(result, "foo quux baz");
     }
+
+    #[test]
+    fn test_string_match() {
+        use crate::interpreter::assert_lisp;
+        assert_lisp(r#"(string-match "a+b" "xaaab")"#, "1");
+        assert_lisp(r#"(string-match "a+c" "xaaab")"#, "nil");
+        assert_lisp(r#"(string-match "[0-9]+$" "abc123")"#, "3");
+        assert_lisp(r#"(string-match "a" "banana" 2)"#, "3");
+        assert_lisp(r#"(string-match "a" "banana" -2)"#, "5");
+        assert_lisp(r#"(string-match "^a" "ba" 1)"#, "nil");
+        // positions are in characters, not bytes
+        assert_lisp(r#"(string-match "b" "λλb")"#, "2");
+        assert_lisp(
+            r#"(progn (string-match "\\(x\\)?\\(b\\)" "λb") (match-data))"#,
+            "(1 2 nil nil 1 2)",
+        );
+        assert_lisp(r#"(progn (string-match "b\\(x\\)?" "λb") (match-data))"#, "(1 2)");
+        assert_lisp(r#"(condition-case nil (string-match "a" "abc" 4) (error 'err))"#, "err");
+    }
+
+    #[test]
+    fn test_split_loop() {
+        use crate::interpreter::assert_lisp;
+        // The loop `split-string' in subr.el uses to walk separators
+        let split = |string: &str, sep: &str| {
+            format!(
+                r#"(let ((string "{string}") (start 0) (list nil))
+                     (while (string-match "{sep}" string start)
+                       (setq list (cons (substring string start (match-beginning 0)) list))
+                       (setq start (match-end 0)))
+                     (nreverse (cons (substring string start) list)))"#
+            )
+        };
+        assert_lisp(&split("a b c", "[ \\t\\n]+"), r#"("a" "b" "c")"#);
+        assert_lisp(&split("a,,b", ","), r#"("a" "" "b")"#);
+        assert_lisp(&split("a,,b", ",+"), r#"("a" "b")"#);
+        assert_lisp(&split("λ,μ", ","), r#"("λ" "μ")"#);
+        assert_lisp(&split("abc", ","), r#"("abc")"#);
+    }
+
+    #[test]
+    fn test_match_data() {
+        crate::interpreter::assert_lisp(
+            r#"(progn (string-match "^[0-9]+\\.\\([0-9]+\\)" "27.1") (list (match-beginning 0) (match-end 0) (match-beginning 1) (match-end 1)))"#,
+            "(0 4 3 4)",
+        );
+        crate::interpreter::assert_lisp(
+            r#"(progn (string-match "b\\(c\\)" "abcd") (substring "abcd" (match-beginning 1) (match-end 1)))"#,
+            r#""c""#,
+        );
+        // `match-string` is defined in subr.el in terms of these
+        crate::interpreter::assert_lisp(
+            r#"(progn (string-match "\\(a+\\)b" "aaab") (list (match-beginning 1) (match-end 1) (substring "aaab" (match-beginning 1) (match-end 1))))"#,
+            r#"(0 3 "aaa")"#,
+        );
+        crate::interpreter::assert_lisp(
+            r#"(progn (string-match "\\(x\\)?b" "ab") (list (match-beginning 0) (match-beginning 1) (match-end 1) (match-beginning 2)))"#,
+            "(1 nil nil nil)",
+        );
+        // a failed match leaves the previous match data in place
+        crate::interpreter::assert_lisp(
+            r#"(progn (string-match "b" "ab") (string-match "z" "ab") (match-data))"#,
+            "(1 2)",
+        );
+        crate::interpreter::assert_lisp(
+            r#"(progn (string-match "b" "ab") (string-match "a" "ab") (match-data))"#,
+            "(0 1)",
+        );
+    }
 }